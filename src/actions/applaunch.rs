@@ -1,23 +1,41 @@
+use std::env;
+use std::path::Path;
 use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::{collections::HashMap, os::fd::AsRawFd};
 
+use gtk4::{gdk, prelude::*};
+
 use regex::Regex;
 
 use crate::{
     sher_log, sherlock_error,
     utils::{
-        config::ConfigGuard,
+        config::{ConfigGuard, LaunchStrategy},
         errors::{SherlockError, SherlockErrorType},
     },
 };
 
+/// Placeholder of the argument bar shown for apps that accept files or URLs. The typed value is
+/// passed to the app through its `%f`, `%F`, `%u` or `%U` field code.
+pub const TARGETS_PLACEHOLDER: &str = "Files or URLs";
+
+/// Values for the field codes of a desktop entry's `Exec` key
+#[derive(Debug, Default)]
+pub struct ExecContext<'a> {
+    pub name: Option<&'a str>,
+    pub icon: Option<&'a str>,
+    pub desktop_file: Option<&'a str>,
+    pub targets: Vec<String>,
+}
+
 pub fn applaunch(
     exec: &str,
     terminal: bool,
     variables: HashMap<String, String>,
+    context: &ExecContext,
 ) -> Result<(), SherlockError> {
     let config = ConfigGuard::read()?;
-    let mut parts = Vec::new();
     let mut exec = exec.to_string();
 
     // Insert prefixes
@@ -39,26 +57,52 @@ pub fn applaunch(
         exec = exec.replace(&format!("{{variable:{}}}", k), &v);
     }
 
+    let mut base = Vec::new();
     if let Some(pre) = &config.behavior.global_prefix {
-        parts.push(pre.to_string());
+        base.extend(split_as_command(pre));
     }
     if terminal {
-        parts.push(config.default_apps.terminal.clone());
-        parts.push("-e".to_string());
-    }
-    parts.push(exec.to_string());
-    if let Some(flag) = &config.behavior.global_flags {
-        parts.push(flag.to_string());
+        base.extend(split_as_command(&config.default_apps.terminal));
+        base.push("-e".to_string());
     }
+    let flags = config
+        .behavior
+        .global_flags
+        .as_deref()
+        .map(split_as_command)
+        .unwrap_or_default();
 
-    let cmd = parts.join(" ").trim().to_string();
-    let mut parts = split_as_command(&cmd).into_iter();
+    let app_id = context
+        .desktop_file
+        .map(Path::new)
+        .and_then(|path| path.file_stem())
+        .and_then(|stem| stem.to_str())
+        .map(str::to_string);
 
-    let mut command = Command::new(parts.next().ok_or(sherlock_error!(
-        SherlockErrorType::CommandExecutionError(cmd.clone()),
-        format!("Failed to get first base command")
-    ))?);
-    command.args(parts);
+    // Apps that only take a single file or URL are started once per target
+    let args = split_exec(&exec);
+    let takes = |codes: &[&str]| {
+        args.iter()
+            .any(|(arg, quoted)| !quoted && codes.iter().any(|code| arg.contains(code)))
+    };
+    let single_only = takes(&["%f", "%u"]) && !takes(&["%F", "%U"]);
+    let runs: Vec<Vec<String>> = if single_only && context.targets.len() > 1 {
+        context.targets.iter().map(|t| vec![t.clone()]).collect()
+    } else {
+        vec![context.targets.clone()]
+    };
+
+    for targets in runs {
+        let mut parts = base.clone();
+        parts.extend(expand_field_codes(&args, context, &targets));
+        parts.extend(flags.iter().cloned());
+        spawn(parts, app_id.as_deref())?;
+    }
+    Ok(())
+}
+
+fn spawn(parts: Vec<String>, app_id: Option<&str>) -> Result<(), SherlockError> {
+    let (command, cmd) = build_command(parts, app_id)?;
 
     match launch_detached(command) {
         Ok(_) => {
@@ -76,6 +120,224 @@ pub fn applaunch(
     }
 }
 
+/// Wraps the command according to the configured launch strategy and hands an activation
+/// token to the started process. Returns the command together with its printable form.
+pub fn build_command(
+    parts: Vec<String>,
+    app_id: Option<&str>,
+) -> Result<(Command, String), SherlockError> {
+    let config = ConfigGuard::read()?;
+    let parts = match config.behavior.launch_strategy {
+        LaunchStrategy::Direct => parts,
+        LaunchStrategy::Systemd => {
+            let mut wrapped = vec![
+                String::from("systemd-run"),
+                String::from("--user"),
+                String::from("--scope"),
+                String::from("--quiet"),
+                String::from("--slice=app-graphical.slice"),
+            ];
+            if let Some(app_id) = app_id {
+                wrapped.push(format!("--unit={}", scope_name(app_id)));
+            }
+            wrapped.push(String::from("--"));
+            wrapped.extend(parts);
+            wrapped
+        }
+        LaunchStrategy::Uwsm => ["uwsm", "app", "--"]
+            .into_iter()
+            .map(str::to_string)
+            .chain(parts)
+            .collect(),
+        LaunchStrategy::Custom => match &config.behavior.launch_template {
+            Some(template) => {
+                let mut wrapped = Vec::new();
+                let mut inserted = false;
+                for arg in split_as_command(template) {
+                    if arg == "{cmd}" {
+                        wrapped.extend(parts.iter().cloned());
+                        inserted = true;
+                    } else {
+                        wrapped.push(arg);
+                    }
+                }
+                if !inserted {
+                    wrapped.extend(parts);
+                }
+                wrapped
+            }
+            None => parts,
+        },
+    };
+
+    let cmd = parts.join(" ");
+    let mut parts = parts.into_iter();
+    let mut command = Command::new(parts.next().ok_or(sherlock_error!(
+        SherlockErrorType::CommandExecutionError(cmd.clone()),
+        format!("Failed to get first base command")
+    ))?);
+    command.args(parts);
+
+    if let Some(token) = activation_token() {
+        command.env("XDG_ACTIVATION_TOKEN", &token);
+        command.env("DESKTOP_STARTUP_ID", &token);
+    }
+
+    Ok((command, cmd))
+}
+
+/// Requests an xdg-activation token from the compositor, so the started app may take focus
+fn activation_token() -> Option<String> {
+    env::var_os("WAYLAND_DISPLAY")?;
+    let display = gdk::Display::default()?;
+    display
+        .app_launch_context()
+        .startup_notify_id(None::<&gio::AppInfo>, &[])
+        .map(|token| token.to_string())
+}
+
+/// Scope names follow `app-<launcher>-<app id>-<random>.scope` from the systemd desktop
+/// environment conventions.
+fn scope_name(app_id: &str) -> String {
+    let app_id: String = app_id
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '_' | ':' => c,
+            _ => '_',
+        })
+        .collect();
+    let random = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos())
+        ^ std::process::id();
+    format!("app-sherlock-{}-{:08x}.scope", app_id, random)
+}
+
+/// Whether the `Exec` line takes files or URLs as arguments
+pub fn accepts_targets(exec: &str) -> bool {
+    split_exec(exec).iter().any(|(arg, quoted)| {
+        !quoted
+            && ["%f", "%F", "%u", "%U"]
+                .iter()
+                .any(|code| arg.contains(code))
+    })
+}
+
+/// Splits an `Exec` value into arguments. Double quotes follow the desktop entry
+/// specification, single quotes are kept for compatibility with hand written commands.
+/// The flag marks arguments that contained quotes, as field codes are not expanded in those.
+fn split_exec(exec: &str) -> Vec<(String, bool)> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_arg = false;
+    let mut chars = exec.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                quoted = true;
+                in_arg = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if matches!(chars.peek(), Some('"' | '`' | '$' | '\\')) => {
+                            current.extend(chars.next());
+                        }
+                        _ => current.push(c),
+                    }
+                }
+            }
+            '\'' => {
+                quoted = true;
+                in_arg = true;
+                current.extend(chars.by_ref().take_while(|c| *c != '\''));
+            }
+            '\\' => {
+                in_arg = true;
+                current.extend(chars.next());
+            }
+            c if c.is_whitespace() => {
+                if in_arg {
+                    args.push((std::mem::take(&mut current), quoted));
+                }
+                quoted = false;
+                in_arg = false;
+            }
+            _ => {
+                in_arg = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_arg {
+        args.push((current, quoted));
+    }
+    args
+}
+
+/// Expands field codes as defined by the desktop entry specification. Codes without a value
+/// and deprecated codes are removed.
+fn expand_field_codes(
+    args: &[(String, bool)],
+    context: &ExecContext,
+    targets: &[String],
+) -> Vec<String> {
+    let mut expanded = Vec::with_capacity(args.len());
+    for (arg, quoted) in args {
+        if *quoted {
+            expanded.push(arg.clone());
+            continue;
+        }
+        match arg.as_str() {
+            "%F" => expanded.extend(targets.iter().map(|t| to_path(t))),
+            "%U" => expanded.extend(targets.iter().cloned()),
+            "%i" => {
+                if let Some(icon) = context.icon {
+                    expanded.push(String::from("--icon"));
+                    expanded.push(icon.to_string());
+                }
+            }
+            _ => {
+                let mut result = String::with_capacity(arg.len());
+                let mut only_codes = true;
+                let mut chars = arg.chars();
+                while let Some(c) = chars.next() {
+                    if c != '%' {
+                        only_codes = false;
+                        result.push(c);
+                        continue;
+                    }
+                    let value = match chars.next() {
+                        Some('%') => Some(String::from("%")),
+                        Some('f' | 'F') => targets.first().map(|t| to_path(t)),
+                        Some('u' | 'U') => targets.first().cloned(),
+                        Some('c') => context.name.map(str::to_string),
+                        Some('k') => context.desktop_file.map(str::to_string),
+                        _ => None,
+                    };
+                    result.extend(value);
+                }
+                if !result.is_empty() || !only_codes {
+                    expanded.push(result);
+                }
+            }
+        }
+    }
+    expanded
+}
+
+/// `%f` and `%F` expect local paths
+pub fn to_path(target: &str) -> String {
+    target
+        .strip_prefix("file://")
+        .map(|path| {
+            percent_encoding::percent_decode_str(path)
+                .decode_utf8_lossy()
+                .into_owned()
+        })
+        .unwrap_or_else(|| target.to_string())
+}
+
 pub fn launch_detached(mut command: Command) -> std::io::Result<()> {
     unsafe {
         match libc::fork() {
@@ -186,3 +448,45 @@ pub fn split_as_command(cmd: &str) -> Vec<String> {
 
     parts
 }
+
+#[test]
+fn test_field_codes() {
+    let context = ExecContext {
+        name: Some("Files"),
+        icon: Some("org.gnome.Nautilus"),
+        desktop_file: Some("/usr/share/applications/org.gnome.Nautilus.desktop"),
+        targets: vec![],
+    };
+    let targets = [
+        String::from("file:///tmp/a%20b"),
+        String::from("https://x.org"),
+    ];
+
+    let args = split_exec(r#"nautilus %U %i --class=%c "keep %f" --file=%k %% %d"#);
+    assert_eq!(
+        expand_field_codes(&args, &context, &targets),
+        vec![
+            "nautilus",
+            "file:///tmp/a%20b",
+            "https://x.org",
+            "--icon",
+            "org.gnome.Nautilus",
+            "--class=Files",
+            "keep %f",
+            "--file=/usr/share/applications/org.gnome.Nautilus.desktop",
+            "%",
+        ]
+    );
+    let args = split_exec("gimp %f");
+    assert_eq!(
+        expand_field_codes(&args, &context, &targets[..1]),
+        vec!["gimp", "/tmp/a b"]
+    );
+    assert_eq!(expand_field_codes(&args, &context, &[]), vec!["gimp"]);
+
+    let args = split_exec(r#"sh -c "echo \"hi\" \$HOME""#);
+    assert_eq!(
+        expand_field_codes(&args, &context, &[]),
+        vec!["sh", "-c", r#"echo "hi" $HOME"#]
+    );
+}
@@ -1,9 +1,11 @@
 use regex::Regex;
 use std::collections::HashMap;
 use std::io::Write;
-use std::process::{Command, Stdio};
+use std::process::Stdio;
 
-use crate::actions::applaunch::{launch_detached, launch_detached_child, split_as_command};
+use crate::actions::applaunch::{
+    build_command, launch_detached, launch_detached_child, split_as_command,
+};
 use crate::api::api::SherlockAPI;
 use crate::sher_log;
 use crate::utils::config::ConfigGuard;
@@ -99,31 +101,23 @@ pub fn asynchronous_execution(
 
     sher_log!(format!(r#"Spawning command "{}""#, raw_command))?;
 
-    let mut parts = split_as_command(&raw_command).into_iter();
-    let base = parts.next().ok_or_else(|| {
-        sherlock_error!(
+    let mut parts = split_as_command(&raw_command);
+    if parts.is_empty() {
+        return Err(sherlock_error!(
             SherlockErrorType::CommandExecutionError(raw_command.clone()),
             "Failed to get first base command"
-        )
-    })?;
+        ));
+    }
 
     // If sudo is requested, wrap the command
-    let mut command = if let Some(ref _pw) = sudo_password {
-        let mut c = Command::new("sudo");
-
+    if sudo_password.is_some() {
         // -S makes sudo read password from stdin
-        c.arg("-S");
-
-        c.arg(base);
-        c.args(parts);
-
-        c.stdin(Stdio::piped());
-        c
-    } else {
-        let mut c = Command::new(base);
-        c.args(parts);
-        c
-    };
+        parts.splice(0..0, [String::from("sudo"), String::from("-S")]);
+    }
+    let (mut command, _) = build_command(parts, None)?;
+    if sudo_password.is_some() {
+        command.stdin(Stdio::piped());
+    }
 
     // If sudo: write password into stdin *before* detaching
     if let Some(ref pw) = sudo_password {
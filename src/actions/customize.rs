@@ -0,0 +1,380 @@
+use gio::glib::MainContext;
+use glob::Pattern;
+use serde::de::{MapAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::env;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+
+use super::applaunch::{accepts_targets, applaunch, ExecContext};
+use crate::api::api::SherlockAPI;
+use crate::loader::application_loader::{desktop_file_id, get_applications_dir, get_data_dirs};
+use crate::loader::desktop_entry::{DesktopEntry, Locale};
+use crate::loader::mime_apps::MIME_APPS;
+use crate::loader::util::ApplicationAction;
+use crate::sherlock_error;
+use crate::utils::config::ConfigGuard;
+use crate::utils::errors::{SherlockError, SherlockErrorType};
+
+/// Context actions to customize an app. `desktop_file` is passed to every action as its
+/// target, the unaliased name as its `exec`.
+pub fn customize_actions(desktop_file: &Path) -> Vec<ApplicationAction> {
+    let Some(path) = desktop_file.to_str() else {
+        return vec![];
+    };
+    let Some(name) = original_name(desktop_file) else {
+        return vec![];
+    };
+    [
+        ("hide_app", "Hide App", "view-conceal"),
+        ("rename_app", "Rename…", "edit-rename"),
+        ("set_app_icon", "Change Icon…", "preferences-desktop-icons"),
+        ("add_app_keywords", "Add Keywords…", "edit-find"),
+        ("edit_desktop_file", "Edit Desktop File", "text-editor"),
+        (
+            "show_desktop_file",
+            "Show Desktop File Location",
+            "folder-open",
+        ),
+    ]
+    .into_iter()
+    .map(|(method, label, icon)| {
+        let mut action = ApplicationAction::new(method);
+        action.name = Some(label.to_string());
+        action.exec = Some(name.clone());
+        action.icon = Some(icon.to_string());
+        action.target = Some(path.to_string());
+        action
+    })
+    .collect()
+}
+
+/// The name aliases and ignore patterns are matched against
+fn original_name(desktop_file: &Path) -> Option<String> {
+    DesktopEntry::read(desktop_file)
+        .ok()?
+        .group("Desktop Entry")?
+        .localized("Name", Locale::from_env().as_ref())
+}
+
+/// Appends the app to `sherlockignore`
+pub fn hide_app(name: &str) -> Result<(), SherlockError> {
+    let config = ConfigGuard::read()?;
+    let path = &config.files.ignore;
+    let write_err = |e: std::io::Error| {
+        sherlock_error!(
+            SherlockErrorType::FileWriteError(path.clone()),
+            e.to_string()
+        )
+    };
+
+    // Patterns are matched case insensitively against the lowercase name
+    let pattern = Pattern::escape(&name.to_lowercase());
+    let needs_newline = fs::read_to_string(path)
+        .map(|content| !content.is_empty() && !content.ends_with('\n'))
+        .unwrap_or(false);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(write_err)?;
+    let line = if needs_newline {
+        format!("\n{}\n", pattern)
+    } else {
+        format!("{}\n", pattern)
+    };
+    file.write_all(line.as_bytes()).map_err(write_err)
+}
+
+/// Sets `key` of the app's entry in `sherlock_alias.json`. An empty value removes the key.
+/// Other entries and keys are kept as they are.
+fn set_alias(name: &str, key: &str, value: &str) -> Result<(), SherlockError> {
+    let config = ConfigGuard::read()?;
+    let path = &config.files.alias;
+    let mut aliases = read_aliases(path)?;
+
+    let fields = aliases.get_or_default(name);
+    let value = value.trim();
+    if value.is_empty() {
+        fields.remove(key);
+    } else {
+        fields.insert(key, Value::String(value.to_string()));
+    }
+    if fields.0.is_empty() {
+        aliases.remove(name);
+    }
+
+    let content = serde_json::to_string_pretty(&aliases).map_err(|e| {
+        sherlock_error!(
+            SherlockErrorType::FileWriteError(path.clone()),
+            e.to_string()
+        )
+    })?;
+    fs::write(path, content).map_err(|e| {
+        sherlock_error!(
+            SherlockErrorType::FileWriteError(path.clone()),
+            e.to_string()
+        )
+    })
+}
+
+/// Asks for the new value of a `sherlock_alias.json` field. Returns `false` if the input
+/// was cancelled.
+pub fn edit_alias(method: &str, name: &str, desktop_file: &Path) -> Result<bool, SherlockError> {
+    let placeholder = match method {
+        "rename_app" => format!("New name for {}", name),
+        "set_app_icon" => String::from("Icon name or path"),
+        _ => String::from("Keywords separated by ;"),
+    };
+    let Ok(value) =
+        MainContext::default().block_on(SherlockAPI::input_field(false, Some(&placeholder)))
+    else {
+        return Ok(false);
+    };
+    match method {
+        "rename_app" => set_alias(name, "name", &value)?,
+        "set_app_icon" => set_alias(name, "icon", &value)?,
+        _ => add_keywords(name, desktop_file, &value)?,
+    }
+    Ok(true)
+}
+
+/// Adds keywords to the ones the app is currently found by
+fn add_keywords(name: &str, desktop_file: &Path, keywords: &str) -> Result<(), SherlockError> {
+    let config = ConfigGuard::read()?;
+    let current = read_aliases(&config.files.alias)?
+        .get(name)
+        .and_then(|alias| alias.get("keywords"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        // Alias keywords replace the ones of the desktop file
+        .or_else(|| {
+            let entry = DesktopEntry::read(desktop_file).ok()?;
+            let group = entry.group("Desktop Entry")?;
+            Some(
+                group
+                    .localized_list("Keywords", Locale::from_env().as_ref())
+                    .join(";"),
+            )
+        })
+        .unwrap_or_default();
+    let keywords: Vec<&str> = current
+        .split(';')
+        .chain(keywords.split([';', ',']))
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .collect();
+    set_alias(name, "keywords", &keywords.join(";"))
+}
+
+fn read_aliases(path: &Path) -> Result<OrderedMap<OrderedMap<Value>>, SherlockError> {
+    match fs::read_to_string(path) {
+        Ok(content) if content.trim().is_empty() => Ok(OrderedMap::default()),
+        Ok(content) => serde_json::from_str(&content).map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::FileParseError(path.to_path_buf()),
+                e.to_string()
+            )
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(OrderedMap::default()),
+        Err(e) => Err(sherlock_error!(
+            SherlockErrorType::FileReadError(path.to_path_buf()),
+            e.to_string()
+        )),
+    }
+}
+
+/// A JSON object that keeps the order of its keys, so that rewriting `sherlock_alias.json`
+/// leaves everything but the edited entry as it was
+struct OrderedMap<V>(Vec<(String, V)>);
+impl<V> Default for OrderedMap<V> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+impl<V> OrderedMap<V> {
+    fn get(&self, key: &str) -> Option<&V> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+    fn insert(&mut self, key: &str, value: V) {
+        match self.0.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => self.0.push((key.to_string(), value)),
+        }
+    }
+    fn remove(&mut self, key: &str) {
+        self.0.retain(|(k, _)| k != key);
+    }
+}
+impl<V: Default> OrderedMap<V> {
+    fn get_or_default(&mut self, key: &str) -> &mut V {
+        let index = match self.0.iter().position(|(k, _)| k == key) {
+            Some(index) => index,
+            None => {
+                self.0.push((key.to_string(), V::default()));
+                self.0.len() - 1
+            }
+        };
+        &mut self.0[index].1
+    }
+}
+impl<V: Serialize> Serialize for OrderedMap<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, v)))
+    }
+}
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for OrderedMap<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OrderedVisitor<V>(PhantomData<V>);
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for OrderedVisitor<V> {
+            type Value = OrderedMap<V>;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON object")
+            }
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                let mut map = OrderedMap::default();
+                while let Some((key, value)) = access.next_entry::<String, V>()? {
+                    map.insert(&key, value);
+                }
+                Ok(map)
+            }
+        }
+        deserializer.deserialize_map(OrderedVisitor(PhantomData))
+    }
+}
+
+/// Opens the desktop file in the text editor
+pub fn edit_desktop_file(desktop_file: &Path) -> Result<(), SherlockError> {
+    let file = editable_desktop_file(desktop_file)?;
+    let (exec, terminal) = text_editor();
+    let context = ExecContext {
+        targets: vec![file.display().to_string()],
+        ..Default::default()
+    };
+    applaunch(&exec, terminal, HashMap::new(), &context)
+}
+
+/// Highlights the desktop file in the file manager, or opens its directory if the file
+/// manager does not implement `org.freedesktop.FileManager1`
+pub fn show_desktop_file(desktop_file: &Path) -> Result<(), SherlockError> {
+    if show_in_file_manager(desktop_file) {
+        return Ok(());
+    }
+    let Some(dir) = desktop_file.parent() else {
+        return Ok(());
+    };
+    let exec = MIME_APPS
+        .read()
+        .ok()
+        .and_then(|apps| apps.default_app("inode/directory"))
+        .map(|app| app.exec)
+        .filter(|exec| accepts_targets(exec))
+        .unwrap_or_else(|| String::from("xdg-open %f"));
+    let context = ExecContext {
+        targets: vec![dir.display().to_string()],
+        ..Default::default()
+    };
+    applaunch(&exec, false, HashMap::new(), &context)
+}
+
+/// Desktop files outside of `$XDG_DATA_HOME/applications` are copied there first. The copy
+/// has the same desktop file ID and therefore takes precedence over the original.
+fn editable_desktop_file(desktop_file: &Path) -> Result<PathBuf, SherlockError> {
+    let Some(user_dir) = get_data_dirs()
+        .into_iter()
+        .next()
+        .map(|dir| dir.join("applications"))
+    else {
+        return Ok(desktop_file.to_path_buf());
+    };
+    if desktop_file.starts_with(&user_dir) {
+        return Ok(desktop_file.to_path_buf());
+    }
+    let Some(id) = desktop_file_id(desktop_file, &get_applications_dir()) else {
+        return Ok(desktop_file.to_path_buf());
+    };
+
+    let copy = user_dir.join(id);
+    if !copy.exists() {
+        fs::create_dir_all(&user_dir).map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::DirCreateError(user_dir.display().to_string()),
+                e.to_string()
+            )
+        })?;
+        fs::copy(desktop_file, &copy).map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::FileWriteError(copy.clone()),
+                e.to_string()
+            )
+        })?;
+    }
+    Ok(copy)
+}
+
+/// The default text editor, or `$VISUAL`/`$EDITOR` inside the terminal
+fn text_editor() -> (String, bool) {
+    let default_app = MIME_APPS
+        .read()
+        .ok()
+        .and_then(|apps| apps.default_app("text/plain"));
+    if let Some(app) = default_app {
+        return (app.exec, app.terminal);
+    }
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| String::from("vi"));
+    (format!("{} %f", editor), true)
+}
+
+fn show_in_file_manager(file: &Path) -> bool {
+    let Ok(conn) = zbus::blocking::Connection::session() else {
+        return false;
+    };
+    let uri = gio::File::for_path(file).uri().to_string();
+    conn.call_method(
+        Some("org.freedesktop.FileManager1"),
+        "/org/freedesktop/FileManager1",
+        Some("org.freedesktop.FileManager1"),
+        "ShowItems",
+        &(vec![uri], ""),
+    )
+    .is_ok()
+}
+
+/// Removes the app cache so customizations are picked up on the next start
+pub fn invalidate_app_cache() -> Result<(), SherlockError> {
+    let config = ConfigGuard::read()?;
+    match fs::remove_file(&config.caching.cache) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(sherlock_error!(
+            SherlockErrorType::FileRemoveError(config.caching.cache.clone()),
+            e.to_string()
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn test_alias_order() {
+    let content = r#"{"Zed": {"name": "Editor", "icon": "zed"}, "Alacritty": {"name": "Term"}}"#;
+    let mut aliases: OrderedMap<OrderedMap<Value>> = serde_json::from_str(content).unwrap();
+    aliases
+        .get_or_default("Zed")
+        .insert("keywords", Value::from("code"));
+    aliases.get_or_default("Alacritty").remove("name");
+    aliases.remove("Alacritty");
+    aliases
+        .get_or_default("Firefox")
+        .insert("name", Value::from("Web"));
+
+    // Keys keep the order of the file, new ones are appended
+    assert_eq!(
+        serde_json::to_string(&aliases).unwrap(),
+        r#"{"Zed":{"name":"Editor","icon":"zed","keywords":"code"},"Firefox":{"name":"Web"}}"#
+    );
+}
@@ -1,24 +1,30 @@
 use gio::glib::{object::IsA, variant::ToVariant};
 use gtk4::{prelude::*, Widget};
 use std::fs::File;
+use std::path::Path;
 use std::{collections::HashMap, rc::Rc};
 use teamslaunch::teamslaunch;
 use util::{clear_cached_files, reset_app_counter};
 
 use crate::launcher::{Launcher, LauncherType};
 use crate::{
-    actions::commandlaunch::command_launch,
+    actions::{applaunch::split_as_command, commandlaunch::command_launch},
     api::{call::ApiCall, server::SherlockServer},
     daemon::daemon::print_reponse,
     g_subclasses::action_entry::ContextAction,
-    launcher::{process_launcher::ProcessLauncher, theme_picker::ThemePicker},
-    loader::util::CounterReader,
+    launcher::{
+        calc_launcher::expression::Evaluation, generate_launcher::Generator, plugin_launcher,
+        process_launcher::ProcessLauncher, recent_files_launcher::RecentFilesLauncher,
+        theme_picker::ThemePicker,
+    },
+    loader::{pins::PINS, util::CounterReader},
     sherlock_error,
     utils::{config::ConfigGuard, errors::SherlockErrorType, files::home_dir},
 };
 
 pub mod applaunch;
 pub mod commandlaunch;
+pub mod customize;
 pub mod teamslaunch;
 pub mod util;
 pub mod websearch;
@@ -48,14 +54,37 @@ pub fn execute_from_attrs<T: IsA<Widget>>(
                     let _ = row.activate_action("win.clear-search", Some(&false.to_variant()));
                 });
             }
-            "app_launcher" => {
+            "app_launcher" | "recent_files" => {
                 let exec = attrs.get("exec").map_or("", |s| s.as_str());
                 let term = attrs.get("term").map_or(false, |s| s.as_str() == "true");
-                if let Err(error) = applaunch::applaunch(exec, term, variables) {
+                // Files or URLs either come from the argument bar or from the caller
+                let mut targets: Vec<String> = attrs
+                    .get("targets")
+                    .map(|t| t.lines().map(str::to_string).collect())
+                    .unwrap_or_default();
+                if let Some(typed) = variables.get(applaunch::TARGETS_PLACEHOLDER) {
+                    let home = home_dir().ok();
+                    targets.extend(split_as_command(typed).into_iter().map(|target| {
+                        match (target.strip_prefix("~/"), &home) {
+                            (Some(rest), Some(home)) => home.join(rest).display().to_string(),
+                            _ => target,
+                        }
+                    }));
+                }
+                let context = applaunch::ExecContext {
+                    name: attrs.get("name").map(String::as_str),
+                    icon: attrs.get("icon").map(String::as_str),
+                    desktop_file: attrs.get("desktop_file").map(String::as_str),
+                    targets,
+                };
+                if let Err(error) = applaunch::applaunch(exec, term, variables, &context) {
                     exit = false;
                     let _result = error.insert(false);
                 }
-                increment(&exec);
+                // Recent files are ordered by the history itself
+                if method == "app_launcher" {
+                    increment(&exec);
+                }
             }
             "web_launcher" | "bookmarks" => {
                 let engine = attrs.get("engine").map_or("plain", |s| s.as_str());
@@ -84,7 +113,7 @@ pub fn execute_from_attrs<T: IsA<Widget>>(
                     increment(&exec);
                 }
             }
-            "copy" => {
+            "copy" | "calc_history" | "transform" => {
                 if let Ok(config) = ConfigGuard::read() {
                     let field = attrs.get("field").or(config.runtime.field.as_ref());
                     if let Some(field) = field {
@@ -102,10 +131,29 @@ pub fn execute_from_attrs<T: IsA<Widget>>(
             "print" => {
                 if let Some(field) = attrs.get("field") {
                     if let Some(output) = attrs.get(field) {
-                        let _result = print_reponse(output);
+                        let _result = print_reponse(util::format_output(output, attrs));
                     }
                 } else if let Some(output) = attrs.get("result").or(attrs.get("exec")) {
-                    let _result = print_reponse(output);
+                    let _result = print_reponse(util::format_output(output, attrs));
+                }
+            }
+            "calc_ans" => {
+                exit = false;
+                if let Some(result) = attrs.get("exec") {
+                    // Numbers are reused as `ans`, anything else is inserted as is
+                    let query = match result.parse::<f64>() {
+                        Ok(value) => {
+                            Evaluation {
+                                value,
+                                assign: None,
+                            }
+                            .commit();
+                            String::from("ans")
+                        }
+                        Err(_) => result.clone(),
+                    };
+                    let _ = row.activate_action("win.switch-mode", Some(&"all".to_variant()));
+                    let _ = row.activate_action("win.set-search", Some(&query.to_variant()));
                 }
             }
             "teams_event" => {
@@ -118,6 +166,25 @@ pub fn execute_from_attrs<T: IsA<Widget>>(
                     }
                 }
             }
+            "generate" => {
+                let password_length = match launcher.as_deref().map(|l| &l.launcher_type) {
+                    Some(LauncherType::Generate(gen)) => gen.password_length,
+                    _ => 20,
+                };
+                // Values are only held in memory and never passed to the logger
+                let copied = attrs
+                    .get("keyword")
+                    .and_then(|keyword| Generator::parse(keyword, password_length))
+                    .map(|generator| {
+                        generator
+                            .generate()
+                            .and_then(|value| util::copy_to_clipboard(&value))
+                    });
+                if let Some(Err(error)) = copied {
+                    exit = false;
+                    let _result = error.insert(false);
+                }
+            }
             "emoji_picker" => {
                 exit = false;
 
@@ -155,6 +222,64 @@ pub fn execute_from_attrs<T: IsA<Widget>>(
                 let _ = row
                     .activate_action("win.add-page", Some(&next_content.to_string().to_variant()));
             }
+            "hide_app" | "rename_app" | "set_app_icon" | "add_app_keywords"
+            | "edit_desktop_file" => {
+                let name = attrs.get("exec").map_or("", |s| s.as_str());
+                let desktop_file = attrs.get("targets").map_or("", |s| s.as_str());
+                let result = match method.as_str() {
+                    "hide_app" => customize::hide_app(name).map(|_| true),
+                    "edit_desktop_file" => {
+                        customize::edit_desktop_file(Path::new(desktop_file)).map(|_| true)
+                    }
+                    method => customize::edit_alias(method, name, Path::new(desktop_file)),
+                };
+                let result = result.and_then(|changed| {
+                    if changed {
+                        customize::invalidate_app_cache()?;
+                    }
+                    Ok(changed)
+                });
+                match result {
+                    Ok(true) => {
+                        let _ = row.activate_action(
+                            "win.reload-apps",
+                            Some(&desktop_file.to_string().to_variant()),
+                        );
+                    }
+                    // Cancelled input
+                    Ok(false) => exit = false,
+                    Err(error) => {
+                        exit = false;
+                        let _result = error.insert(false);
+                    }
+                }
+            }
+            "show_desktop_file" => {
+                if let Some(desktop_file) = attrs.get("targets") {
+                    if let Err(error) = customize::show_desktop_file(Path::new(desktop_file)) {
+                        exit = false;
+                        let _result = error.insert(false);
+                    }
+                }
+            }
+            "pin" => {
+                if let (Some(key), Ok(mut pins)) = (attrs.get("exec"), PINS.write()) {
+                    pins.toggle(key);
+                    if let Err(error) = pins.save() {
+                        let _result = error.insert(false);
+                    }
+                    drop(pins);
+                    let _ = row.activate_action("win.update-items", Some(&false.to_variant()));
+                }
+            }
+            "remove_recent" => {
+                if let Some(href) = attrs.get("exec") {
+                    if let Err(error) = RecentFilesLauncher::remove(href) {
+                        exit = false;
+                        let _result = error.insert(false);
+                    }
+                }
+            }
             "kill-process" => {
                 if let Some((ppid, cpid)) = attrs
                     .get("parent-pid")
@@ -233,6 +358,14 @@ pub fn execute_from_attrs<T: IsA<Widget>>(
                     _ => {}
                 }
             }
+            "plugin" => {
+                // Closing is deferred until the plugin handled the activation
+                let close = do_exit.unwrap_or(exit);
+                if let Some(payload) = attrs.get("exec") {
+                    plugin_launcher::activate(payload, close);
+                }
+                return;
+            }
             "clear_cache" => {
                 let _result = clear_cached_files();
             }
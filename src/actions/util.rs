@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::fs;
 
 use cli_clipboard::{ClipboardContext, ClipboardProvider};
+use serde_json::json;
 
 use crate::sherlock_error;
-use crate::utils::config::ConfigGuard;
+use crate::utils::config::{ConfigGuard, OutputFormat};
 use crate::utils::{
     errors::{SherlockError, SherlockErrorType},
     paths,
@@ -16,6 +18,26 @@ pub fn copy_to_clipboard(string: &str) -> Result<(), SherlockError> {
     let _ = ctx.set_contents(string.to_string());
     Ok(())
 }
+/// Formats a selected result according to the `--output-format` flag. Json output carries
+/// the element's hidden fields alongside the result so scripts don't have to re-parse it.
+pub fn format_output(output: &str, attrs: &HashMap<String, String>) -> String {
+    let format = ConfigGuard::read().map_or(OutputFormat::default(), |c| c.runtime.output_format);
+    match format {
+        OutputFormat::Newline => format!("{}\n", output),
+        OutputFormat::Null => format!("{}\0", output),
+        OutputFormat::Json => {
+            let hidden: HashMap<String, String> = attrs
+                .get("hidden")
+                .and_then(|h| serde_json::from_str(h).ok())
+                .unwrap_or_default();
+            let value = json!({
+                "result": output,
+                "hidden": hidden,
+            });
+            format!("{}\n", value)
+        }
+    }
+}
 //TODO: takes 2.9ms/1.6ms - how to improve
 #[sherlock_macro::timing(level = "launchers")]
 pub fn read_from_clipboard() -> Result<String, SherlockError> {
@@ -49,6 +71,16 @@ pub fn clear_cached_files() -> Result<(), SherlockError> {
 
 pub fn reset_app_counter() -> Result<(), SherlockError> {
     let data_dir = paths::get_data_dir()?;
+    let usage_path = data_dir.join("usage.bin");
+    match fs::remove_file(&usage_path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            return Err(sherlock_error!(
+                SherlockErrorType::FileRemoveError(usage_path),
+                e.to_string()
+            ));
+        }
+        _ => {}
+    }
     let counts_path = data_dir.join("counts.json");
     fs::remove_file(&counts_path).map_err(|e| {
         sherlock_error!(
@@ -41,10 +41,11 @@ pub fn websearch(
         Some(b) => b.to_string(),
         None => {
             let c = ConfigGuard::read()?;
+            // Only look up the default browser if none is configured
             c.default_apps
                 .browser
                 .clone()
-                .unwrap_or(ConstantDefaults::browser()?)
+                .map_or_else(ConstantDefaults::browser, Ok)?
         }
     };
 
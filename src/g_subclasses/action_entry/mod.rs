@@ -54,6 +54,9 @@ impl ContextAction {
             let exec = action.exec.clone();
             let method = action.method.clone();
             let exit = action.exit.clone();
+            let target = action.target.clone();
+            let icon = action.icon.clone();
+            let terminal = terminal || action.terminal;
             move |args| {
                 let row = args.first().map(|f| f.get::<ContextAction>().ok())??;
                 let attrs = get_attrs_map(vec![
@@ -61,6 +64,8 @@ impl ContextAction {
                     ("exec", exec.as_deref()),
                     ("term", Some(&terminal.to_string())),
                     ("exit", Some(&exit.to_string())),
+                    ("targets", target.as_deref()),
+                    ("icon", icon.as_deref()),
                 ]);
 
                 let exit: u8 = args.get(1).and_then(|v| v.get::<u8>().ok())?;
@@ -24,6 +24,8 @@ pub struct TileItem {
     pub actions: RefCell<Vec<ApplicationAction>>,
     pub vars: RefCell<Vec<ExecVariable>>,
     pub binds: Rc<RefCell<Vec<SherlockRowBind>>>,
+    /// Key in `pins.json`, computed on first use
+    pub pin_key: RefCell<Option<Option<String>>>,
 }
 
 // The central trait for subclassing a GObject
@@ -2,6 +2,7 @@ mod imp;
 
 use gio::glib::object::Cast;
 use gio::glib::{object::ObjectExt, WeakRef};
+use gio::ListStore;
 use glib::Object;
 use gtk4::prelude::WidgetExt;
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
@@ -9,8 +10,13 @@ use gtk4::{glib, Box as GtkBox, Widget};
 use simd_json::prelude::Indexed;
 use std::{rc::Rc, usize};
 
+use crate::actions::applaunch::{accepts_targets, TARGETS_PLACEHOLDER};
+use crate::actions::customize::customize_actions;
 use crate::g_subclasses::sherlock_row::SherlockRowBind;
 use crate::launcher::LauncherType;
+use crate::loader::application_loader::{desktop_file_id, get_applications_dir};
+use crate::loader::mime_apps::open_with_actions;
+use crate::loader::pins::PINS;
 use crate::loader::util::{ApplicationAction, ExecVariable};
 use crate::prelude::TileHandler;
 use crate::ui::tiles::api_tile::ApiTileHandler;
@@ -18,10 +24,13 @@ use crate::ui::tiles::app_tile::AppTileHandler;
 use crate::ui::tiles::calc_tile::CalcTileHandler;
 use crate::ui::tiles::clipboard_tile::ClipboardHandler;
 use crate::ui::tiles::event_tile::EventTileHandler;
+use crate::ui::tiles::generate_tile::GenerateTileHandler;
+use crate::ui::tiles::most_used_tile::MostUsedTileHandler;
 use crate::ui::tiles::mpris_tile::MusicTileHandler;
 use crate::ui::tiles::pipe_tile::PipeTileHandler;
 use crate::ui::tiles::pomodoro_tile::PomodoroTileHandler;
 use crate::ui::tiles::process_tile::ProcTileHandler;
+use crate::ui::tiles::transform_tile::TransformTileHandler;
 use crate::ui::tiles::weather_tile::WeatherTileHandler;
 use crate::ui::tiles::web_tile::WebTileHandler;
 use crate::ui::tiles::Tile;
@@ -34,9 +43,11 @@ glib::wrapper! {
 impl TileItem {
     pub fn set_index<T: TryInto<u16>>(&self, index: T) {
         self.imp().index.replace(index.try_into().ok());
+        self.imp().pin_key.take();
     }
     pub fn set_launcher(&self, launcher: Rc<Launcher>) {
         self.imp().launcher.replace(launcher);
+        self.imp().pin_key.take();
     }
     pub fn set_parent(&self, parent: Option<&SherlockRow>) {
         let imp = self.imp();
@@ -79,10 +90,12 @@ impl TileItem {
             // App Tile Based
             LauncherType::App(_)
             | LauncherType::Bookmark(_)
+            | LauncherType::CalcHistory(_)
             | LauncherType::Category(_)
             | LauncherType::Command(_)
             | LauncherType::Emoji(_)
             | LauncherType::File(_)
+            | LauncherType::RecentFiles(_)
             | LauncherType::Theme(_) => {
                 // Get app data value
                 let inner = launcher.inner()?;
@@ -91,11 +104,19 @@ impl TileItem {
                 Some(tile.upcast::<Widget>())
             }
             LauncherType::Api(api) => {
-                let tile = Tile::api(launcher.clone(), &api);
+                let tile = Tile::api(launcher.clone(), Some(&api.icon));
+                Some(tile.upcast::<Widget>())
+            }
+            LauncherType::Plugin(plugin) => {
+                let tile = Tile::api(launcher.clone(), plugin.icon().as_deref());
+                Some(tile.upcast::<Widget>())
+            }
+            LauncherType::Script(_) => {
+                let tile = Tile::api(launcher.clone(), launcher.icon.as_deref());
                 Some(tile.upcast::<Widget>())
             }
             LauncherType::Clipboard(clp) => {
-                if let Some((tile, handler)) = Tile::clipboard(launcher.clone(), &clp) {
+                if let Some((tile, handler)) = Tile::clipboard(launcher.clone(), &clp, index) {
                     self.imp().update_handler.replace(handler);
                     Some(tile)
                 } else {
@@ -110,6 +131,14 @@ impl TileItem {
                 let tile = Tile::event(evt)?;
                 Some(tile.upcast::<Widget>())
             }
+            LauncherType::Generate(_) => {
+                let tile = Tile::generate(launcher.clone());
+                Some(tile.upcast::<Widget>())
+            }
+            LauncherType::MostUsed(most_used) => {
+                let tile = Tile::most_used(&launcher, most_used)?;
+                Some(tile.upcast::<Widget>())
+            }
             LauncherType::MusicPlayer(_) => {
                 let tile = Tile::mpris_tile();
                 Some(tile.upcast::<Widget>())
@@ -129,6 +158,11 @@ impl TileItem {
                 let tile = Tile::process(value, launcher.clone(), self);
                 Some(tile.upcast::<Widget>())
             }
+            LauncherType::Transform(trf) => {
+                let operation = trf.operations.get(index? as usize)?;
+                let tile = Tile::transform(*operation);
+                Some(tile.upcast::<Widget>())
+            }
             LauncherType::Weather(_) => {
                 let tile = Tile::weather();
                 Some(tile.upcast::<Widget>())
@@ -180,22 +214,33 @@ impl TileItem {
     }
     pub fn num_actions(&self) -> usize {
         let imp = self.imp();
+        // Calculator actions depend on the current result
+        if let UpdateHandler::Calculator(inner) = &*imp.update_handler.borrow() {
+            return inner.actions().len();
+        }
         if let Some(index) = imp.index.get() {
             imp.launcher
                 .borrow()
                 .inner()
                 .and_then(|inner| inner.get(index as usize))
                 .map_or(0, |val| val.actions.len())
+                + self.open_with().len()
+                + self.customize().len()
+                + self.pin_action().is_some() as usize
         } else {
             imp.launcher
                 .borrow()
                 .actions
                 .as_ref()
                 .map_or(0, |a| a.len())
+                + self.pin_action().is_some() as usize
         }
     }
     pub fn actions(&self) -> Vec<ApplicationAction> {
         let imp = self.imp();
+        if let UpdateHandler::Calculator(inner) = &*imp.update_handler.borrow() {
+            return inner.actions();
+        }
         let actions = if let Some(index) = imp.index.get() {
             imp.launcher
                 .borrow()
@@ -205,20 +250,109 @@ impl TileItem {
         } else {
             imp.launcher.borrow().actions.clone()
         };
-        actions.unwrap_or_default()
+        let mut actions = actions.unwrap_or_default();
+        actions.extend(self.open_with());
+        actions.extend(self.customize());
+        actions.extend(self.pin_action());
+        actions
     }
-    pub fn variables(&self) -> Vec<ExecVariable> {
+    /// Identifies the item in `pins.json`. The key is cached, as sorters ask for it on every
+    /// comparison.
+    pub fn pin_key(&self) -> Option<String> {
         let imp = self.imp();
-        if let Some(index) = imp.index.get() {
-            imp.launcher
-                .borrow()
-                .inner()
-                .and_then(|inner| inner.get(index as usize))
-                .map(|val| val.vars.clone())
-                .unwrap_or_default()
+        if let Some(key) = imp.pin_key.borrow().as_ref() {
+            return key.clone();
+        }
+        let key = self.build_pin_key();
+        imp.pin_key.replace(Some(key.clone()));
+        key
+    }
+    fn build_pin_key(&self) -> Option<String> {
+        let launcher = self.imp().launcher.borrow();
+        match &launcher.launcher_type {
+            LauncherType::App(_) => self
+                .get_by_key(|data| data.desktop_file.clone())
+                .flatten()
+                .and_then(|file| desktop_file_id(&file, &get_applications_dir()))
+                .map(|id| format!("app:{}", id)),
+            LauncherType::Bookmark(_) => self
+                .get_by_key(|data| data.exec.clone())
+                .flatten()
+                .map(|url| format!("bookmark:{}", url)),
+            LauncherType::Command(_) => self
+                .get_by_key(|data| data.exec.clone())
+                .flatten()
+                .map(|exec| format!("command:{}", exec)),
+            LauncherType::Pipe(pipe) => pipe.id.as_ref().map(|id| format!("pipe:{}", id)),
+            _ => None,
+        }
+    }
+    /// Position among the pinned items
+    pub fn pin_index(&self) -> Option<usize> {
+        let key = self.pin_key()?;
+        PINS.read().ok()?.position(&key)
+    }
+    fn pin_action(&self) -> Option<ApplicationAction> {
+        let key = self.pin_key()?;
+        let pinned = PINS.read().ok()?.position(&key).is_some();
+        let mut action = ApplicationAction::new("pin");
+        action.name = Some(String::from(if pinned {
+            "Unpin from Home"
         } else {
-            vec![]
+            "Pin to Home"
+        }));
+        action.exec = Some(key);
+        action.icon = Some(String::from("view-pin"));
+        action.exit = false;
+        Some(action)
+    }
+    /// Apps can be hidden, renamed and edited without a text editor
+    fn customize(&self) -> Vec<ApplicationAction> {
+        let enabled = matches!(
+            &self.imp().launcher.borrow().launcher_type,
+            LauncherType::App(app) if app.customize
+        );
+        if !enabled {
+            return vec![];
+        }
+        self.get_by_key(|data| data.desktop_file.clone())
+            .flatten()
+            .map(|file| customize_actions(&file))
+            .unwrap_or_default()
+    }
+    /// Files and URLs can be opened with every app that supports their MIME type
+    fn open_with(&self) -> Vec<ApplicationAction> {
+        let is_target = matches!(
+            self.imp().launcher.borrow().launcher_type,
+            LauncherType::Bookmark(_) | LauncherType::RecentFiles(_)
+        );
+        if !is_target {
+            return vec![];
+        }
+        self.get_by_key(|data| data.target.clone().or_else(|| data.exec.clone()))
+            .flatten()
+            .map(|target| open_with_actions(&target))
+            .unwrap_or_default()
+    }
+    pub fn variables(&self) -> Vec<ExecVariable> {
+        let imp = self.imp();
+        let Some(index) = imp.index.get() else {
+            return vec![];
+        };
+        let launcher = imp.launcher.borrow();
+        let Some(value) = launcher.inner().and_then(|inner| inner.get(index as usize)) else {
+            return vec![];
+        };
+        let mut vars = value.vars.clone();
+        // Apps that open files or URLs get an optional argument bar, unless the file is given
+        let takes_targets = launcher.method == "app_launcher"
+            && value.target.is_none()
+            && value.exec.as_deref().is_some_and(accepts_targets);
+        let placeholder = ExecVariable::StringInput(TARGETS_PLACEHOLDER.to_string());
+        if takes_targets && !vars.contains(&placeholder) {
+            vars.push(placeholder);
         }
+        vars
     }
     pub fn alias(&self) -> String {
         self.imp()
@@ -255,6 +389,16 @@ impl TileItem {
     pub fn active(&self) -> bool {
         self.imp().active.get()
     }
+    /// Whether the row can be marked and executed alongside others outside of multi mode
+    pub fn supports_bulk(&self) -> bool {
+        matches!(
+            self.imp().launcher.borrow().launcher_type,
+            LauncherType::App(_)
+                | LauncherType::Bookmark(_)
+                | LauncherType::Command(_)
+                | LauncherType::File(_)
+        )
+    }
     pub fn based_show(&self, keyword: &str) -> bool {
         let imp = self.imp();
         match &*imp.update_handler.borrow() {
@@ -266,10 +410,23 @@ impl TileItem {
                     false
                 }
             }
+            UpdateHandler::Generate(inner) => inner.based_show(keyword),
+            UpdateHandler::Transform(inner) => {
+                let launcher = imp.launcher.borrow();
+                if let LauncherType::Transform(trf) = &launcher.launcher_type {
+                    imp.index
+                        .get()
+                        .and_then(|i| trf.operations.get(i as usize))
+                        .is_some_and(|operation| inner.based_show(trf.source(keyword), *operation))
+                } else {
+                    false
+                }
+            }
 
             UpdateHandler::AppTile(_)
             | UpdateHandler::Clipboard(_)
             | UpdateHandler::Event(_)
+            | UpdateHandler::MostUsed(_)
             | UpdateHandler::MusicPlayer(_)
             | UpdateHandler::Pipe(_)
             | UpdateHandler::Pomodoro(_)
@@ -315,6 +472,8 @@ impl TileItem {
                     }
                 }
             }
+            UpdateHandler::Generate(inner) => return inner.update(keyword),
+            UpdateHandler::Transform(inner) => return inner.update(keyword),
             UpdateHandler::Weather(inner) => {
                 if let Some(parent) = self.parent().upgrade() {
                     let launcher = imp.launcher.borrow();
@@ -353,6 +512,12 @@ impl TileItem {
             _ => None,
         }
     }
+    /// Gives async tiles access to the model they live in, so they can add result tiles
+    pub fn set_model(&self, model: &ListStore) {
+        if let UpdateHandler::ApiTile(inner) = &*self.imp().update_handler.borrow() {
+            inner.set_model(model);
+        }
+    }
     pub fn change_attrs(&self, key: String, val: String) {
         match &*self.imp().update_handler.borrow() {
             UpdateHandler::ApiTile(inner) => inner.change_attrs(key, val),
@@ -360,9 +525,12 @@ impl TileItem {
             UpdateHandler::Calculator(inner) => inner.change_attrs(key, val),
             UpdateHandler::Clipboard(inner) => inner.change_attrs(key, val),
             UpdateHandler::Event(inner) => inner.change_attrs(key, val),
+            UpdateHandler::Generate(inner) => inner.change_attrs(key, val),
+            UpdateHandler::MostUsed(inner) => inner.change_attrs(key, val),
             UpdateHandler::MusicPlayer(inner) => inner.change_attrs(key, val),
             UpdateHandler::Pipe(inner) => inner.change_attrs(key, val),
             UpdateHandler::Process(inner) => inner.change_attrs(key, val),
+            UpdateHandler::Transform(inner) => inner.change_attrs(key, val),
             UpdateHandler::Weather(inner) => inner.change_attrs(key, val),
             UpdateHandler::WebTile(inner) => inner.change_attrs(key, val),
             UpdateHandler::Pomodoro(_) | UpdateHandler::Default => {}
@@ -377,6 +545,8 @@ impl TileItem {
             UpdateHandler::Calculator(inner) => inner.bind_signal(row, launcher),
             UpdateHandler::Clipboard(inner) => inner.bind_signal(row, launcher),
             UpdateHandler::Event(inner) => inner.bind_signal(row, launcher),
+            UpdateHandler::Generate(inner) => inner.bind_signal(row, launcher),
+            UpdateHandler::MostUsed(inner) => inner.bind_signal(row, launcher),
             UpdateHandler::MusicPlayer(inner) => {
                 if let LauncherType::MusicPlayer(mpris) =
                     &self.imp().launcher.borrow().launcher_type
@@ -391,6 +561,7 @@ impl TileItem {
                 }
             }
             UpdateHandler::Process(inner) => inner.bind_signal(row, launcher),
+            UpdateHandler::Transform(inner) => inner.bind_signal(row, launcher),
             UpdateHandler::Weather(inner) => inner.bind_signal(row, launcher),
             UpdateHandler::WebTile(inner) => inner.bind_signal(row, launcher),
             UpdateHandler::Default => {}
@@ -405,18 +576,29 @@ impl TileItem {
             UpdateHandler::AppTile(inner) => inner.shortcut(),
             UpdateHandler::Clipboard(inner) => inner.shortcut(),
             UpdateHandler::Event(inner) => inner.shortcut(),
+            UpdateHandler::Generate(inner) => inner.shortcut(),
             UpdateHandler::MusicPlayer(inner) => inner.shortcut(),
             UpdateHandler::Pipe(inner) => inner.shortcut(),
             UpdateHandler::Pomodoro(inner) => inner.shortcut(),
             UpdateHandler::Process(inner) => inner.shortcut(),
+            UpdateHandler::Transform(inner) => inner.shortcut(),
             UpdateHandler::WebTile(inner) => inner.shortcut(),
 
             UpdateHandler::ApiTile(_)
             | UpdateHandler::Calculator(_)
+            | UpdateHandler::MostUsed(_)
             | UpdateHandler::Weather(_)
             | UpdateHandler::Default => None,
         }
     }
+    /// Shortcut holders of the tile. Grids have one for every cell.
+    pub fn shortcuts(&self) -> Vec<GtkBox> {
+        let enabled = self.imp().launcher.borrow().shortcut;
+        match &*self.imp().update_handler.borrow() {
+            UpdateHandler::MostUsed(inner) if enabled => inner.shortcuts(),
+            _ => self.shortcut().into_iter().collect(),
+        }
+    }
 
     // Constructors
     pub fn from(launcher: Rc<Launcher>) -> Self {
@@ -439,10 +621,13 @@ pub enum UpdateHandler {
     Calculator(CalcTileHandler),
     Clipboard(ClipboardHandler),
     Event(EventTileHandler),
+    Generate(GenerateTileHandler),
+    MostUsed(MostUsedTileHandler),
     MusicPlayer(MusicTileHandler),
     Pipe(PipeTileHandler),
     Pomodoro(PomodoroTileHandler),
     Process(ProcTileHandler),
+    Transform(TransformTileHandler),
     Weather(WeatherTileHandler),
     WebTile(WebTileHandler),
     Default,
@@ -460,10 +645,13 @@ impl UpdateHandler {
             Self::Calculator(inner) => inner.replace_tile(tile),
             Self::Clipboard(inner) => inner.replace_tile(tile),
             Self::Event(inner) => inner.replace_tile(tile),
+            Self::Generate(inner) => inner.replace_tile(tile),
+            Self::MostUsed(inner) => inner.replace_tile(tile),
             Self::MusicPlayer(inner) => inner.replace_tile(tile),
             Self::Pipe(inner) => inner.replace_tile(tile),
             Self::Pomodoro(inner) => inner.replace_tile(tile),
             Self::Process(inner) => inner.replace_tile(tile),
+            Self::Transform(inner) => inner.replace_tile(tile),
             Self::Weather(inner) => inner.replace_tile(tile),
             Self::WebTile(inner) => inner.replace_tile(tile),
             Self::Default => {}
@@ -3,4 +3,9 @@ use crate::loader::util::AppData;
 #[derive(Clone, Debug)]
 pub struct AppLauncher {
     pub apps: Vec<AppData>,
+    /// Kept to parse desktop files that change while Sherlock is running
+    pub priority: f32,
+    pub use_keywords: bool,
+    /// Whether apps offer context actions to hide, rename or edit them
+    pub customize: bool,
 }
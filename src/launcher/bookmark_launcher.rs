@@ -190,6 +190,8 @@ impl MozillaSqliteParser {
                 for row in rows.flatten() {
                     let bookmark = AppData {
                         name: row.0.to_string(),
+                        generic_name: None,
+                        comment: None,
                         icon: None,
                         icon_class: raw
                             .args
@@ -197,6 +199,7 @@ impl MozillaSqliteParser {
                             .and_then(|v| v.as_str())
                             .map(|s| s.to_string()),
                         exec: Some(row.1),
+                        target: None,
                         search_string: row.0,
                         tag_start: raw.tag_start.clone(),
                         tag_end: raw.tag_end.clone(),
@@ -286,6 +289,8 @@ impl ChromeParser {
                     if let Some(url) = bookmark.url {
                         bookmarks.push(AppData {
                             name: bookmark.name.clone(),
+                            generic_name: None,
+                            comment: None,
                             icon: None,
                             icon_class: raw
                                 .args
@@ -293,6 +298,7 @@ impl ChromeParser {
                                 .and_then(|v| v.as_str())
                                 .map(|s| s.to_string()),
                             exec: Some(url.clone()),
+                            target: None,
                             search_string: format!("{};{}", bookmark.name, url),
                             tag_start: raw.tag_start.clone(),
                             tag_end: raw.tag_end.clone(),
@@ -1,27 +1,59 @@
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
 use std::env::home_dir;
+use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncReadExt, BufReader};
 use tokio::process::Command;
 use tokio::time::{timeout, Duration};
 
+use super::stdio_worker::{StdioWorker, WorkerError};
 use crate::loader::util::ApplicationAction;
 
+/// Persistent workers are shared by their command line so that they stay warm across
+/// launcher reloads (e.g. when sherlock is running as a daemon).
+static WORKERS: Lazy<Mutex<HashMap<String, Arc<BulkTextWorker>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
 #[derive(Clone, Debug)]
 pub struct BulkTextLauncher {
     pub icon: String,
     pub exec: String,
-    pub args: String,
+    pub args: Vec<String>,
+    pub timeout: Duration,
+    pub persistent: bool,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct AsyncCommandResponse {
     pub title: Option<String>,
     pub content: Option<String>,
     pub next_content: Option<String>,
     pub result: Option<String>,
+    pub icon: Option<String>,
+    pub method: Option<String>,
+    pub hidden: Option<HashMap<String, String>>,
     pub actions: Option<Vec<ApplicationAction>>,
 }
+
+/// Scripts can either fill the launcher's own tile or return an array of results, each of
+/// which is shown as a separate tile.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AsyncCommandOutput {
+    Multiple(Vec<AsyncCommandResponse>),
+    Single(AsyncCommandResponse),
+}
+impl From<AsyncCommandResponse> for AsyncCommandOutput {
+    fn from(response: AsyncCommandResponse) -> Self {
+        Self::Single(response)
+    }
+}
+
 impl AsyncCommandResponse {
     fn new() -> Self {
         AsyncCommandResponse {
@@ -30,27 +62,63 @@ impl AsyncCommandResponse {
             next_content: None,
             actions: None,
             result: None,
+            icon: None,
+            method: None,
+            hidden: None,
         }
     }
+    fn error<T: Into<String>>(title: &str, content: T) -> AsyncCommandOutput {
+        let mut response = AsyncCommandResponse::new();
+        response.title = Some(title.to_string());
+        response.content = Some(content.into());
+        response.into()
+    }
+}
+
+/// A single line a persistent worker writes to its stdout. The `id` has to match the id
+/// of the query it answers. Multiple results are passed using the `results` array.
+#[derive(Debug, Deserialize)]
+struct WorkerResponse {
+    #[serde(default)]
+    results: Option<Vec<AsyncCommandResponse>>,
+    #[serde(flatten)]
+    response: AsyncCommandResponse,
+}
+impl WorkerResponse {
+    fn output(self) -> AsyncCommandOutput {
+        match self.results {
+            Some(results) => AsyncCommandOutput::Multiple(results),
+            None => AsyncCommandOutput::Single(self.response),
+        }
+    }
+}
+
+#[derive(Default)]
+struct BulkTextWorker {
+    io: Mutex<Option<Arc<StdioWorker>>>,
+    latest: AtomicU64,
+    /// The query still waiting for its response
+    pending: Mutex<Option<u64>>,
 }
 
 impl BulkTextLauncher {
-    pub async fn get_result(&self, keyword: &str) -> Option<AsyncCommandResponse> {
-        if self.args.contains("{keyword}") && keyword.trim().is_empty() {
+    pub async fn get_result(&self, keyword: &str) -> Option<AsyncCommandOutput> {
+        if self.persistent {
+            if keyword.trim().is_empty() {
+                return None;
+            }
+            return self.query_worker(keyword).await;
+        }
+
+        if self.args.iter().any(|a| a.contains("{keyword}")) && keyword.trim().is_empty() {
             return None;
         };
 
-        let a = self.args.replace("{keyword}", &keyword);
-        let args = a.split(" ");
+        let args = self.args.iter().map(|a| a.replace("{keyword}", &keyword));
 
         // build execution command
-        let home = home_dir()?;
-        let exec = self.exec.clone();
-        let relative_exec = exec.strip_prefix("~/").unwrap_or(&exec);
-        let absolute_exec = home.join(relative_exec);
-        let exec_str = absolute_exec.to_str()?;
-
-        let mut cmd = Command::new(exec_str);
+        let exec_path = self.exec_path()?;
+        let mut cmd = Command::new(exec_path);
 
         cmd.args(args);
         cmd.stdin(Stdio::null())
@@ -61,15 +129,15 @@ impl BulkTextLauncher {
         let mut child = match cmd.spawn() {
             Ok(child) => child,
             Err(e) => {
-                let mut response = AsyncCommandResponse::new();
-                response.title = Some(String::from("Failed to execute script."));
-                response.content = Some(format!("Error: {}", e));
-                return Some(response);
+                return Some(AsyncCommandResponse::error(
+                    "Failed to execute script.",
+                    format!("Error: {}", e),
+                ));
             }
         };
 
         // Set up a timeout for the process
-        let result = timeout(Duration::from_secs(2), async {
+        let result = timeout(self.timeout, async {
             let stdout = child.stdout.take();
             let mut stdout_content = String::new();
             let stderr_content = String::new();
@@ -91,32 +159,120 @@ impl BulkTextLauncher {
             Ok((Ok(status), stdout, _stderr)) => {
                 if status.success() {
                     let mut output = stdout.into_bytes();
-                    let response: AsyncCommandResponse =
-                        simd_json::from_slice(&mut output).unwrap_or(AsyncCommandResponse::new());
+                    let response: AsyncCommandOutput = simd_json::from_slice(&mut output)
+                        .unwrap_or(AsyncCommandResponse::new().into());
                     Some(response)
                 } else {
-                    let mut response = AsyncCommandResponse::new();
-                    response.title = Some(String::from("Script returned an error."));
-                    response.content = Some(format!("Status: {:?}", status));
-                    return Some(response);
+                    Some(AsyncCommandResponse::error(
+                        "Script returned an error.",
+                        format!("Status: {:?}", status),
+                    ))
                 }
             }
-            Ok((Err(_), _, _)) => {
+            Ok((Err(e), _, _)) => {
                 let _ = child.kill().await; // Kill the process if it fails
-                let mut response = AsyncCommandResponse::new();
-                response.title = Some(String::from("Failed to execute script."));
-                response.content = Some(String::from(
-                    "Error occurred while running the process: {:?}",
-                ));
-                Some(response)
+                Some(AsyncCommandResponse::error(
+                    "Failed to execute script.",
+                    format!("Error occurred while running the process: {:?}", e),
+                ))
             }
             Err(_) => {
                 let _ = child.kill().await; // Kill the process on timeout
-                let mut response = AsyncCommandResponse::new();
-                response.title = Some(String::from("Failed to execute script."));
-                response.content = Some(String::from("Timeout exceeded."));
-                Some(response)
+                Some(AsyncCommandResponse::error(
+                    "Failed to execute script.",
+                    "Timeout exceeded.",
+                ))
             }
         }
     }
+
+    /// Sends the keyword to the persistent worker as `{"id": n, "query": "..."}` and waits
+    /// for the response carrying the same id. Queries that got superseded by a newer one
+    /// are cancelled with `{"id": n, "cancel": true}` and their late responses dropped.
+    async fn query_worker(&self, keyword: &str) -> Option<AsyncCommandOutput> {
+        let worker = self.worker();
+        let id = worker.latest.fetch_add(1, Ordering::SeqCst) + 1;
+
+        let io = match self.worker_io(&worker) {
+            Ok(io) => io,
+            Err(e) => {
+                return Some(AsyncCommandResponse::error(
+                    "Failed to execute script.",
+                    format!("Error: {}", e),
+                ))
+            }
+        };
+
+        // Cancel the previous query right away instead of after its response
+        let stale = worker.pending.lock().ok().and_then(|mut p| p.replace(id));
+        if let Some(stale) = stale {
+            io.cancel(stale);
+            let _ = io.write(&json!({"id": stale, "cancel": true})).await;
+        }
+
+        let message = json!({"id": id, "query": keyword});
+        let result = io.request(id, &message, self.timeout).await;
+        if let Ok(mut pending) = worker.pending.lock() {
+            if *pending == Some(id) {
+                *pending = None;
+            }
+        }
+
+        match result {
+            Ok(value) => match serde_json::from_value::<WorkerResponse>(value) {
+                Ok(response) => Some(response.output()),
+                Err(e) => Some(AsyncCommandResponse::error(
+                    "Invalid worker response.",
+                    format!("Error: {}", e),
+                )),
+            },
+            // Superseded by a newer query
+            Err(WorkerError::Cancelled) => None,
+            Err(WorkerError::Io(e)) => {
+                // Restart the worker on the next query
+                if let Ok(mut guard) = worker.io.lock() {
+                    if guard
+                        .as_ref()
+                        .is_some_and(|current| Arc::ptr_eq(current, &io))
+                    {
+                        *guard = None;
+                    }
+                }
+                Some(AsyncCommandResponse::error(
+                    "Worker stopped unexpectedly.",
+                    e,
+                ))
+            }
+            Err(WorkerError::Timeout) => Some(AsyncCommandResponse::error(
+                "Failed to execute script.",
+                "Timeout exceeded.",
+            )),
+        }
+    }
+    fn worker(&self) -> Arc<BulkTextWorker> {
+        let key = format!("{} {}", self.exec, self.args.join(" "));
+        let mut workers = WORKERS.lock().unwrap();
+        workers.entry(key).or_default().clone()
+    }
+    /// The running worker process, spawned if it is not running (anymore)
+    fn worker_io(&self, worker: &BulkTextWorker) -> std::io::Result<Arc<StdioWorker>> {
+        let mut guard = worker
+            .io
+            .lock()
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        if let Some(io) = guard.as_ref().filter(|io| !io.is_closed()) {
+            return Ok(io.clone());
+        }
+        let exec_path = self
+            .exec_path()
+            .ok_or_else(|| std::io::Error::other("Invalid exec path"))?;
+        let io = Arc::new(StdioWorker::spawn(&exec_path, &self.args)?);
+        *guard = Some(io.clone());
+        Ok(io)
+    }
+    fn exec_path(&self) -> Option<PathBuf> {
+        let home = home_dir()?;
+        let relative_exec = self.exec.strip_prefix("~/").unwrap_or(&self.exec);
+        Some(home.join(relative_exec))
+    }
 }
@@ -0,0 +1,50 @@
+use crate::loader::util::{AppData, ApplicationAction};
+
+use super::calc_launcher::history::{self, HistoryEntry};
+
+/// Lists previously executed calculations. Executing an entry copies its result.
+#[derive(Clone, Debug)]
+pub struct CalcHistoryLauncher {
+    pub entries: Vec<AppData>,
+}
+
+impl CalcHistoryLauncher {
+    pub fn new(prio: f32, limit: usize) -> Self {
+        let entries: Vec<HistoryEntry> = history::read().into_iter().take(limit).collect();
+        let count = entries.len() as f32;
+        let entries = entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let mut data = AppData::new();
+                data.name = format!("{}  {}", entry.query, entry.display);
+                data.search_string = format!("{};{}", entry.query, entry.result);
+                // Newer calculations are listed first
+                data.priority = prio + i as f32 / (count + 1.0);
+                data.actions = vec![
+                    ApplicationAction {
+                        name: Some(String::from("Copy Result")),
+                        exec: Some(entry.result.clone()),
+                        icon: Some(String::from("edit-copy")),
+                        method: String::from("copy"),
+                        exit: true,
+                        target: None,
+                        terminal: false,
+                    },
+                    ApplicationAction {
+                        name: Some(String::from("Insert as ans")),
+                        exec: Some(entry.result.clone()),
+                        icon: Some(String::from("accessories-calculator")),
+                        method: String::from("calc_ans"),
+                        exit: false,
+                        target: None,
+                        terminal: false,
+                    },
+                ];
+                data.exec = Some(entry.result);
+                data
+            })
+            .collect();
+        Self { entries }
+    }
+}
@@ -0,0 +1,471 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs::{create_dir_all, File},
+    path::Path,
+    sync::OnceLock,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    sher_log, sherlock_error,
+    utils::{
+        errors::{SherlockError, SherlockErrorType},
+        files::{expand_path, home_dir},
+    },
+};
+
+pub static CURRENCIES: OnceLock<Option<Currency>> = OnceLock::new();
+
+static ECB_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"currency=['"]([A-Za-z]{3})['"]\s+rate=['"]([0-9.]+)['"]"#).unwrap());
+
+/// ISO code, display symbol and aliases of well-known currencies. Any other code offered
+/// by the provider can still be used by its code.
+const ALIASES: &[(&str, &str, &[&str])] = &[
+    ("usd", "$", &["dollar", "us dollar", "bucks"]),
+    ("eur", "€", &["euro", "euros", "european euro"]),
+    ("jpy", "¥", &["yen", "japanese yen"]),
+    ("gbp", "£", &["pound", "british pound", "pound sterling"]),
+    (
+        "aud",
+        "A$",
+        &["australian dollar", "aussie dollar", "aussie"],
+    ),
+    ("cad", "C$", &["canadian dollar", "loonie"]),
+    ("chf", "CHF", &["swiss franc", "franc"]),
+    ("cny", "¥", &["chinese yuan", "renminbi", "yuan"]),
+    ("nzd", "NZ$", &["new zealand dollar", "kiwi"]),
+    ("sek", "kr", &["swedish krona", "krona"]),
+    ("nok", "kr", &["norwegian krone", "krone"]),
+    ("mxn", "Mex$", &["mexican peso", "peso"]),
+    ("sgd", "S$", &["singapore dollar"]),
+    ("hkd", "HK$", &["hong kong dollar"]),
+    ("krw", "₩", &["south korean won", "won"]),
+    ("pln", "zł", &["polish", "złoty", "polish złoty"]),
+];
+
+/// Source of exchange rates. Configured using the `currency_providers` argument of the
+/// calculator launcher and tried in order.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum CurrencyProvider {
+    /// Daily reference rates of the European Central Bank
+    Ecb {
+        #[serde(default = "CurrencyProvider::ecb_url")]
+        url: String,
+    },
+    /// Any JSON endpoint. The fields are mapped using dot-separated paths.
+    Json {
+        url: String,
+        #[serde(default)]
+        name: Option<String>,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        #[serde(flatten)]
+        mapping: JsonMapping,
+    },
+    /// User-maintained rates file using the same mapping as `json`
+    File {
+        path: String,
+        #[serde(flatten)]
+        mapping: JsonMapping,
+    },
+}
+
+/// Describes where the rates are found in a JSON document
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct JsonMapping {
+    /// Currency the rates are relative to. Takes precedence over `base_path`.
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default = "JsonMapping::base_path")]
+    pub base_path: String,
+    /// Either an object mapping codes to rates or an array of objects
+    #[serde(default = "JsonMapping::rates_path")]
+    pub rates_path: String,
+    #[serde(default = "JsonMapping::code_field")]
+    pub code_field: String,
+    #[serde(default = "JsonMapping::rate_field")]
+    pub rate_field: String,
+    /// Set if rates are given as the value of one unit in the base currency instead of
+    /// units per base currency
+    #[serde(default)]
+    pub inverted: bool,
+}
+impl Default for JsonMapping {
+    fn default() -> Self {
+        Self {
+            base: None,
+            base_path: Self::base_path(),
+            rates_path: Self::rates_path(),
+            code_field: Self::code_field(),
+            rate_field: Self::rate_field(),
+            inverted: false,
+        }
+    }
+}
+impl JsonMapping {
+    fn base_path() -> String {
+        String::from("base")
+    }
+    fn rates_path() -> String {
+        String::from("rates")
+    }
+    fn code_field() -> String {
+        String::from("code")
+    }
+    fn rate_field() -> String {
+        String::from("rate")
+    }
+    fn parse(&self, json: &Value) -> Option<HashMap<String, f64>> {
+        let base = self
+            .base
+            .clone()
+            .or_else(|| lookup(json, &self.base_path)?.as_str().map(str::to_string))
+            .unwrap_or(String::from("usd"));
+        let number = |value: &Value| {
+            value
+                .as_f64()
+                .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        };
+        let rates: HashMap<String, f64> = match lookup(json, &self.rates_path)? {
+            Value::Object(map) => map
+                .iter()
+                .filter_map(|(code, rate)| Some((code.clone(), number(rate)?)))
+                .collect(),
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|item| {
+                    let code = item.get(&self.code_field)?.as_str()?;
+                    Some((code.to_string(), number(item.get(&self.rate_field)?)?))
+                })
+                .collect(),
+            _ => return None,
+        };
+        normalize(&base, rates, self.inverted)
+    }
+}
+
+fn lookup<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|key| !key.is_empty())
+        .try_fold(json, |value, key| match value {
+            Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+            _ => value.get(key),
+        })
+}
+
+/// Converts rates given in units per `base` into the value of one unit in US dollars
+fn normalize(
+    base: &str,
+    rates: HashMap<String, f64>,
+    inverted: bool,
+) -> Option<HashMap<String, f64>> {
+    let mut rates: HashMap<String, f64> = rates
+        .into_iter()
+        .map(|(code, rate)| {
+            (
+                code.to_lowercase(),
+                if inverted { 1.0 / rate } else { rate },
+            )
+        })
+        .filter(|(_, rate)| rate.is_finite() && *rate > 0.0)
+        .collect();
+    rates.insert(base.to_lowercase(), 1.0);
+    let usd = *rates.get("usd")?;
+    Some(
+        rates
+            .into_iter()
+            .map(|(code, rate)| (code, usd / rate))
+            .collect(),
+    )
+}
+
+impl CurrencyProvider {
+    fn ecb_url() -> String {
+        String::from("https://www.ecb.europa.eu/stats/eurofxref/eurofxref-daily.xml")
+    }
+    pub fn defaults() -> Vec<Self> {
+        vec![Self::Ecb {
+            url: Self::ecb_url(),
+        }]
+    }
+    pub fn name(&self) -> String {
+        match self {
+            Self::Ecb { .. } => String::from("ECB"),
+            Self::Json {
+                name: Some(name), ..
+            } => name.clone(),
+            Self::Json { url, .. } => url
+                .split("://")
+                .last()
+                .and_then(|rest| rest.split('/').next())
+                .unwrap_or(url)
+                .to_string(),
+            Self::File { .. } => String::from("rates file"),
+        }
+    }
+    /// Modification time of a rates file. Fetched rates are always up to date.
+    fn modified(&self) -> Option<SystemTime> {
+        match self {
+            Self::File { path, .. } => expand_path(path, &home_dir().ok()?)
+                .metadata()
+                .ok()?
+                .modified()
+                .ok(),
+            _ => None,
+        }
+    }
+    /// Returns the value of one unit in US dollars for every supported currency
+    pub async fn fetch(&self) -> Result<HashMap<String, f64>, SherlockError> {
+        let invalid = |resource: &str| {
+            sherlock_error!(
+                SherlockErrorType::DeserializationError,
+                format!("No exchange rates found in \"{}\"", resource)
+            )
+        };
+        match self {
+            Self::Ecb { url } => {
+                let body = Self::get(url, &HashMap::new()).await?;
+                let rates: HashMap<String, f64> = ECB_RE
+                    .captures_iter(&body)
+                    .filter_map(|caps| Some((caps[1].to_string(), caps[2].parse().ok()?)))
+                    .collect();
+                normalize("eur", rates, false).ok_or_else(|| invalid(url))
+            }
+            Self::Json {
+                url,
+                headers,
+                mapping,
+                ..
+            } => {
+                let body = Self::get(url, headers).await?;
+                let json: Value = serde_json::from_str(&body).map_err(|e| {
+                    sherlock_error!(SherlockErrorType::DeserializationError, e.to_string())
+                })?;
+                mapping.parse(&json).ok_or_else(|| invalid(url))
+            }
+            Self::File { path, mapping } => {
+                let path = expand_path(path, &home_dir()?);
+                let file = File::open(&path).map_err(|e| {
+                    sherlock_error!(
+                        SherlockErrorType::FileReadError(path.clone()),
+                        e.to_string()
+                    )
+                })?;
+                let json: Value = serde_json::from_reader(file).map_err(|e| {
+                    sherlock_error!(SherlockErrorType::DeserializationError, e.to_string())
+                })?;
+                mapping
+                    .parse(&json)
+                    .ok_or_else(|| invalid(&path.to_string_lossy()))
+            }
+        }
+    }
+    async fn get(url: &str, headers: &HashMap<String, String>) -> Result<String, SherlockError> {
+        let request_error = |e: reqwest::Error| {
+            sherlock_error!(
+                SherlockErrorType::HttpRequestError(format!("GET {} || getting currencies", url)),
+                e.to_string()
+            )
+        };
+        let mut request = reqwest::Client::new()
+            .get(url)
+            .timeout(Duration::from_secs(10));
+        for (key, value) in headers {
+            request = request.header(key, value);
+        }
+        request
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(request_error)?
+            .text()
+            .await
+            .map_err(request_error)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Currency {
+    /// Value of one unit in US dollars, keyed by the lowercase ISO 4217 code
+    rates: HashMap<String, f64>,
+    /// Name of the provider the rates were fetched from
+    pub provider: String,
+    /// Time of the fetch in seconds since the unix epoch
+    pub updated: u64,
+}
+impl Currency {
+    pub fn match_unit(&self, unit: &str) -> Option<(f64, String)> {
+        let unit = unit.trim().to_lowercase();
+        let (code, symbol) = match Self::alias(&unit) {
+            Some((code, symbol)) => (code.to_string(), symbol.to_string()),
+            None => (unit.clone(), unit.to_uppercase()),
+        };
+        self.rates.get(&code).map(|rate| (*rate, symbol))
+    }
+    pub fn unit_exists(unit: &str) -> bool {
+        let unit = unit.trim().to_lowercase();
+        Self::alias(&unit).is_some()
+            || (unit.chars().count() == 3 && unit.chars().all(|c| c.is_ascii_alphabetic()))
+    }
+    fn alias(unit: &str) -> Option<(&'static str, &'static str)> {
+        ALIASES
+            .iter()
+            .find(|(code, _, aliases)| *code == unit || aliases.contains(&unit))
+            .map(|(code, symbol, _)| (*code, *symbol))
+    }
+    fn age(&self) -> Duration {
+        let updated = UNIX_EPOCH + Duration::from_secs(self.updated);
+        SystemTime::now()
+            .duration_since(updated)
+            .unwrap_or_default()
+    }
+    /// Short description of the rates' source, e.g. `ECB · 3 hours old`
+    pub fn status(&self) -> String {
+        let minutes = self.age().as_secs() / 60;
+        let age = match minutes {
+            0 => String::from("just updated"),
+            1..60 => format!("{} min old", minutes),
+            60..1440 => format!("{} h old", minutes / 60),
+            _ => format!("{} days old", minutes / 1440),
+        };
+        format!("{} · {}", self.provider, age)
+    }
+
+    fn load_cached<P: AsRef<Path>>(loc: P) -> Option<Currency> {
+        File::open(loc.as_ref())
+            .ok()
+            .and_then(|file| simd_json::from_reader(file).ok())
+    }
+    fn cache<P: AsRef<Path>>(&self, loc: P) -> Result<(), SherlockError> {
+        let absolute = loc.as_ref();
+        if !absolute.is_file() {
+            if let Some(parents) = absolute.parent() {
+                create_dir_all(parents).map_err(|e| {
+                    sherlock_error!(
+                        SherlockErrorType::DirCreateError(String::from(
+                            "~/.cache/sherlock/currency/"
+                        )),
+                        e.to_string()
+                    )
+                })?;
+            }
+        }
+        let content = simd_json::to_string(self)
+            .map_err(|e| sherlock_error!(SherlockErrorType::SerializationError, e.to_string()))?;
+        std::fs::write(absolute, content).map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::FileWriteError(absolute.to_path_buf()),
+                e.to_string()
+            )
+        })
+    }
+
+    /// Returns cached rates younger than `update_interval` seconds. Otherwise, the
+    /// providers are tried in order. Failing providers are reported as warnings and
+    /// outdated rates are used if none of them succeeds.
+    pub async fn get_exchange(
+        providers: &[CurrencyProvider],
+        update_interval: u64,
+    ) -> Result<Currency, SherlockError> {
+        let home = home_dir()?;
+        let absolute = home.join(".cache/sherlock/currency/currency.json");
+        let cached = match Currency::load_cached(&absolute) {
+            // Rates files are cheap to read and may have been edited since
+            Some(curr)
+                if providers.iter().any(|p| {
+                    !matches!(p, CurrencyProvider::File { .. }) && p.name() == curr.provider
+                }) && curr.age() < Duration::from_secs(update_interval) =>
+            {
+                return Ok(curr)
+            }
+            cached => cached,
+        };
+
+        for provider in providers {
+            match provider.fetch().await {
+                Ok(rates) => {
+                    let curr = Currency {
+                        rates,
+                        provider: provider.name(),
+                        updated: provider
+                            .modified()
+                            .unwrap_or_else(SystemTime::now)
+                            .duration_since(UNIX_EPOCH)
+                            .map_or(0, |d| d.as_secs()),
+                    };
+                    curr.cache(absolute)?;
+                    return Ok(curr);
+                }
+                Err(e) => {
+                    let _ = sher_log!(format!(
+                        "Currency provider {} failed: {}",
+                        provider.name(),
+                        e.traceback
+                    ));
+                    let _result = e.insert(false);
+                }
+            }
+        }
+
+        cached.ok_or_else(|| {
+            sherlock_error!(
+                SherlockErrorType::DeserializationError,
+                String::from("No exchange rates available.")
+            )
+        })
+    }
+}
+
+#[test]
+fn test_rate_mapping() {
+    let json: Value = serde_json::json!({
+        "data": {"base": "EUR", "quotes": [{"symbol": "USD", "price": "1.25"}, {"symbol": "JPY", "price": 150}]}
+    });
+    let mapping = JsonMapping {
+        base_path: String::from("data.base"),
+        rates_path: String::from("data.quotes"),
+        code_field: String::from("symbol"),
+        rate_field: String::from("price"),
+        ..Default::default()
+    };
+    let rates = mapping.parse(&json).unwrap();
+    assert_eq!(rates["eur"], 1.25);
+    assert_eq!(rates["usd"], 1.0);
+    assert!((rates["jpy"] - 1.25 / 150.0).abs() < 1e-12);
+}
+
+#[tokio::test]
+async fn test_ecb_provider() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!(
+        "http://{}/eurofxref-daily.xml",
+        listener.local_addr().unwrap()
+    );
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0; 1024];
+        let _ = stream.read(&mut buf).await;
+        let body = "<Cube><Cube time='2026-10-16'><Cube currency='USD' rate='1.1'/>\
+                    <Cube currency='GBP' rate='0.88'/></Cube></Cube>";
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+    });
+
+    let provider = CurrencyProvider::Ecb { url };
+    let rates = provider.fetch().await.unwrap();
+    assert!((rates["eur"] - 1.1).abs() < 1e-12);
+    assert!((rates["gbp"] - 1.1 / 0.88).abs() < 1e-12);
+}
@@ -0,0 +1,409 @@
+use chrono::{
+    DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Timelike, Utc,
+};
+use chrono_tz::{Tz, TZ_VARIANTS};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::format::NumberFormat;
+
+const DISPLAY_FORMAT: &str = "%a, %d %b %Y %H:%M";
+
+static UNIX_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:unix\s+(-?\d+)|(-?\d+)\s+unix)$").unwrap());
+static TO_UNIX_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?:unix\s+(.+)|(.+?)\s+(?:in|to|as)\s+unix)$").unwrap());
+static SPAN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(?P<unit>[a-z]+)\s+)?(?P<dir>until|till|since)\s+(?P<date>.+)$").unwrap()
+});
+static BETWEEN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(?P<unit>[a-z]+)\s+)?between\s+(?P<from>.+?)\s+and\s+(?P<to>.+)$").unwrap()
+});
+static ZONE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?P<from>.+?)\s+(?:in|to)\s+(?P<to>[a-z_/+\-\s]+)$").unwrap());
+static EXPR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?P<base>now|today|tomorrow|yesterday|\d{4}-\d{2}-\d{2}(?:[\st]\d{1,2}:\d{2}(?::\d{2})?)?|\d{1,2}:\d{2}(?::\d{2})?(?:\s*[ap]m)?)(?P<offsets>(?:\s*[+-]\s*\d+\s*[a-z]+)*)$",
+    )
+    .unwrap()
+});
+static OFFSET_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"([+-])\s*(\d+)\s*([a-z]+)").unwrap());
+static AGO_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(\d+)\s*([a-z]+)\s+(ago|from now)$").unwrap());
+
+/// Timezone of a date. `Local` follows the system timezone.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Zone {
+    Local,
+    Named(Tz),
+}
+impl Zone {
+    /// Accepts IANA names like `Europe/Berlin`, their city part like `berlin` or
+    /// `new york`, and `utc` or `local`
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().replace(' ', "_");
+        match name.to_lowercase().as_str() {
+            "" => return None,
+            "local" => return Some(Self::Local),
+            "utc" | "gmt" => return Some(Self::Named(Tz::UTC)),
+            _ => {}
+        }
+        TZ_VARIANTS
+            .iter()
+            .find(|tz| tz.name().eq_ignore_ascii_case(&name))
+            .or_else(|| {
+                TZ_VARIANTS.iter().find(|tz| {
+                    tz.name()
+                        .rsplit('/')
+                        .next()
+                        .is_some_and(|city| city.eq_ignore_ascii_case(&name))
+                })
+            })
+            .map(|tz| Self::Named(*tz))
+    }
+    fn resolve(self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        let local = match self {
+            Self::Local => Local.from_local_datetime(&naive).earliest()?.naive_utc(),
+            Self::Named(tz) => tz.from_local_datetime(&naive).earliest()?.naive_utc(),
+        };
+        Some(Utc.from_utc_datetime(&local))
+    }
+    fn naive(self, instant: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Self::Local => instant.with_timezone(&Local).naive_local(),
+            Self::Named(tz) => instant.with_timezone(&tz).naive_local(),
+        }
+    }
+    fn display(self, instant: DateTime<Utc>) -> String {
+        match self {
+            Self::Local => instant
+                .with_timezone(&Local)
+                .format(DISPLAY_FORMAT)
+                .to_string(),
+            Self::Named(tz) => instant
+                .with_timezone(&tz)
+                .format(&format!("{} %Z", DISPLAY_FORMAT))
+                .to_string(),
+        }
+    }
+    fn rfc3339(self, instant: DateTime<Utc>) -> String {
+        match self {
+            Self::Local => instant.with_timezone(&Local).to_rfc3339(),
+            Self::Named(tz) => instant.with_timezone(&tz).to_rfc3339(),
+        }
+    }
+}
+
+/// Evaluates date and time queries like `now + 90 days`, `days until 2026-12-24`,
+/// `unix 1700000000` or `15:00 berlin in tokyo`. Returns the value to copy and the
+/// text to display.
+pub fn evaluate(keyword: &str) -> Option<(String, String)> {
+    DateContext {
+        now: Utc::now(),
+        local: Zone::Local,
+    }
+    .evaluate(keyword)
+}
+
+struct DateContext {
+    now: DateTime<Utc>,
+    local: Zone,
+}
+impl DateContext {
+    fn evaluate(&self, keyword: &str) -> Option<(String, String)> {
+        let keyword = keyword.trim().to_lowercase();
+
+        if let Some(caps) = UNIX_RE.captures(&keyword) {
+            let value: i64 = caps.get(1).or(caps.get(2))?.as_str().parse().ok()?;
+            // Timestamps with more than 11 digits are most likely milliseconds
+            let instant = if value.abs() >= 100_000_000_000 {
+                DateTime::from_timestamp_millis(value)?
+            } else {
+                DateTime::from_timestamp(value, 0)?
+            };
+            return Some((
+                self.local.rfc3339(instant),
+                format!("= {}", self.local.display(instant)),
+            ));
+        }
+        if let Some(caps) = TO_UNIX_RE.captures(&keyword) {
+            let (instant, _) = self.instant(caps.get(1).or(caps.get(2))?.as_str(), self.local)?;
+            let timestamp = instant.timestamp().to_string();
+            return Some((timestamp.clone(), format!("= {}", timestamp)));
+        }
+        if let Some(caps) = SPAN_RE.captures(&keyword) {
+            let (date, _) = self.instant(&caps["date"], self.local)?;
+            let unit = caps.name("unit").map(|u| u.as_str());
+            return match &caps["dir"] {
+                "since" => self.span(date, self.now, unit),
+                _ => self.span(self.now, date, unit),
+            };
+        }
+        if let Some(caps) = BETWEEN_RE.captures(&keyword) {
+            let (from, _) = self.instant(&caps["from"], self.local)?;
+            let (to, _) = self.instant(&caps["to"], self.local)?;
+            return self.span(from, to, caps.name("unit").map(|u| u.as_str()));
+        }
+        if let Some(caps) = ZONE_RE.captures(&keyword) {
+            if let Some(zone) = Zone::parse(&caps["to"]) {
+                let (instant, _) = self.zoned_instant(&caps["from"])?;
+                return Some((
+                    zone.rfc3339(instant),
+                    format!("= {}", zone.display(instant)),
+                ));
+            }
+        }
+        if let Some((to, from)) = keyword.rsplit_once(" - ") {
+            if let (Some((to, _)), Some((from, _))) =
+                (self.instant(to, self.local), self.instant(from, self.local))
+            {
+                return self.span(from, to, None);
+            }
+        }
+
+        // Plain dates are left to the math parser, e.g. `2026-12-24` is a subtraction
+        let (instant, relative) = self.instant(&keyword, self.local)?;
+        relative.then(|| {
+            (
+                self.local.rfc3339(instant),
+                format!("= {}", self.local.display(instant)),
+            )
+        })
+    }
+
+    /// Parses the source of a timezone conversion. The source may end in a timezone
+    /// of its own, e.g. `15:00 new york`.
+    fn zoned_instant(&self, input: &str) -> Option<(DateTime<Utc>, bool)> {
+        if input == "time" {
+            return Some((self.now, true));
+        }
+        if let Some(result) = self.instant(input, self.local) {
+            return Some(result);
+        }
+        let words: Vec<&str> = input.split_whitespace().collect();
+        (1..words.len().min(4)).find_map(|split| {
+            let (expr, zone) = words.split_at(words.len() - split);
+            let zone = Zone::parse(&zone.join(" "))?;
+            self.instant(&expr.join(" "), zone)
+        })
+    }
+
+    /// Parses a date expression in the given zone. Also returns whether the expression
+    /// was relative to the current time or shifted by an offset.
+    fn instant(&self, input: &str, zone: Zone) -> Option<(DateTime<Utc>, bool)> {
+        let input = input.trim();
+        if let Some(caps) = AGO_RE.captures(input) {
+            let amount: i64 = caps[1].parse().ok()?;
+            let amount = if &caps[3] == "ago" { -amount } else { amount };
+            let naive = shift(zone.naive(self.now), amount, &caps[2])?;
+            return Some((zone.resolve(naive)?, true));
+        }
+
+        let caps = EXPR_RE.captures(input)?;
+        let now = zone.naive(self.now);
+        let base = &caps["base"];
+        let naive = match base {
+            "now" => now,
+            "today" => now.date().and_time(NaiveTime::MIN),
+            "tomorrow" => now.date().succ_opt()?.and_time(NaiveTime::MIN),
+            "yesterday" => now.date().pred_opt()?.and_time(NaiveTime::MIN),
+            _ if base.contains('-') => {
+                let (date, time) = base.split_at(10);
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+                match time.trim_start_matches(['t', ' ']) {
+                    "" => date.and_time(NaiveTime::MIN),
+                    time => date.and_time(parse_time(time)?),
+                }
+            }
+            _ => now.date().and_time(parse_time(base)?),
+        };
+
+        let mut naive = naive;
+        let mut shifted = false;
+        for offset in OFFSET_RE.captures_iter(&caps["offsets"]) {
+            let amount: i64 = offset[2].parse().ok()?;
+            let amount = if &offset[1] == "-" { -amount } else { amount };
+            naive = shift(naive, amount, &offset[3])?;
+            shifted = true;
+        }
+        let relative = shifted || base.chars().all(|c| c.is_ascii_alphabetic());
+        Some((zone.resolve(naive)?, relative))
+    }
+
+    /// Formats the time from `from` to `to` in the given unit. Without a unit, the
+    /// duration is split into days, hours and minutes.
+    fn span(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        unit: Option<&str>,
+    ) -> Option<(String, String)> {
+        let format = NumberFormat::from_config();
+        let duration = to - from;
+        let seconds = duration.num_seconds() as f64;
+        // Whole days are counted in calendar days, so `days until` a date ignores the
+        // current time of day
+        let days = (self.local.naive(to).date() - self.local.naive(from).date()).num_days();
+
+        let (value, name, plural) = match unit.map(unit_name) {
+            None | Some(Some("time")) => {
+                let text = human_duration(duration);
+                return Some((text.clone(), format!("= {}", text)));
+            }
+            Some(Some("second")) => (seconds, "second", "seconds"),
+            Some(Some("minute")) => (seconds / 60.0, "minute", "minutes"),
+            Some(Some("hour")) => (seconds / 3600.0, "hour", "hours"),
+            Some(Some("day")) => (days as f64, "day", "days"),
+            Some(Some("week")) => (days as f64 / 7.0, "week", "weeks"),
+            Some(Some("month")) => (months_between(from, to) as f64, "month", "months"),
+            Some(Some("year")) => ((months_between(from, to) / 12) as f64, "year", "years"),
+            _ => return None,
+        };
+        let name = if value.abs() == 1.0 { name } else { plural };
+        Some((
+            super::format::raw(value),
+            format!("= {} {}", format.format(value, None), name),
+        ))
+    }
+}
+
+fn parse_time(input: &str) -> Option<NaiveTime> {
+    let input = input.trim();
+    let (time, pm) = match input.strip_suffix("am").or(input.strip_suffix("pm")) {
+        Some(time) => (time.trim(), Some(input.ends_with("pm"))),
+        None => (input, None),
+    };
+    let time = NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+        .ok()?;
+    match pm {
+        Some(pm) if time.hour() <= 12 => {
+            let hour = time.hour() % 12 + if pm { 12 } else { 0 };
+            time.with_hour(hour)
+        }
+        Some(_) => None,
+        None => Some(time),
+    }
+}
+
+fn unit_name(unit: &str) -> Option<&'static str> {
+    match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Some("second"),
+        "min" | "mins" | "minute" | "minutes" => Some("minute"),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some("hour"),
+        "d" | "day" | "days" => Some("day"),
+        "w" | "week" | "weeks" => Some("week"),
+        "mo" | "month" | "months" => Some("month"),
+        "y" | "yr" | "yrs" | "year" | "years" => Some("year"),
+        "time" => Some("time"),
+        _ => None,
+    }
+}
+
+/// Shifts a local date. Days and larger units keep the time of day across daylight
+/// saving changes.
+fn shift(naive: NaiveDateTime, amount: i64, unit: &str) -> Option<NaiveDateTime> {
+    let months = |n: i64| u32::try_from(n.unsigned_abs()).ok().map(Months::new);
+    match unit_name(unit)? {
+        "second" => naive.checked_add_signed(Duration::try_seconds(amount)?),
+        "minute" => naive.checked_add_signed(Duration::try_minutes(amount)?),
+        "hour" => naive.checked_add_signed(Duration::try_hours(amount)?),
+        "day" => naive.checked_add_signed(Duration::try_days(amount)?),
+        "week" => naive.checked_add_signed(Duration::try_weeks(amount)?),
+        "month" if amount < 0 => naive.checked_sub_months(months(amount)?),
+        "month" => naive.checked_add_months(months(amount)?),
+        "year" if amount < 0 => naive.checked_sub_months(months(amount.checked_mul(12)?)?),
+        "year" => naive.checked_add_months(months(amount.checked_mul(12)?)?),
+        _ => None,
+    }
+}
+
+fn months_between(from: DateTime<Utc>, to: DateTime<Utc>) -> i64 {
+    let (from, to, sign) = if to < from {
+        (to, from, -1)
+    } else {
+        (from, to, 1)
+    };
+    let mut months =
+        (to.year() - from.year()) as i64 * 12 + to.month() as i64 - from.month() as i64;
+    if (to.day(), to.time()) < (from.day(), from.time()) {
+        months -= 1;
+    }
+    months * sign
+}
+
+fn human_duration(duration: Duration) -> String {
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    let duration = duration.abs();
+    let parts = [
+        (duration.num_days(), "day", "days"),
+        (duration.num_hours() % 24, "hour", "hours"),
+        (duration.num_minutes() % 60, "minute", "minutes"),
+    ];
+    let text: Vec<String> = parts
+        .iter()
+        .filter(|(value, _, _)| *value != 0)
+        .map(|(value, name, plural)| {
+            format!("{} {}", value, if *value == 1 { name } else { plural })
+        })
+        .collect();
+    if text.is_empty() {
+        String::from("0 minutes")
+    } else {
+        format!("{}{}", sign, text.join(", "))
+    }
+}
+
+#[test]
+fn test_datetime() {
+    let ctx = DateContext {
+        now: Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap(),
+        local: Zone::Named(Tz::UTC),
+    };
+    let display = |query: &str| ctx.evaluate(query).map(|(_, text)| text);
+
+    assert_eq!(
+        display("now + 90 days").as_deref(),
+        Some("= Sat, 16 Jan 2027 12:00 UTC")
+    );
+    assert_eq!(
+        display("3 weeks ago").as_deref(),
+        Some("= Sun, 27 Sep 2026 12:00 UTC")
+    );
+    assert_eq!(
+        display("days until 2026-12-24").as_deref(),
+        Some("= 67 days")
+    );
+    assert_eq!(
+        display("time until 2026-10-19 13:30").as_deref(),
+        Some("= 1 day, 1 hour, 30 minutes")
+    );
+    assert_eq!(
+        display("months between 2026-01-31 and 2026-10-18").as_deref(),
+        Some("= 8 months")
+    );
+    assert_eq!(
+        display("unix 1700000000").as_deref(),
+        Some("= Tue, 14 Nov 2023 22:13 UTC")
+    );
+    assert_eq!(
+        ctx.evaluate("2023-11-14 22:13:20 to unix")
+            .map(|(copy, _)| copy),
+        Some(String::from("1700000000"))
+    );
+    assert_eq!(
+        display("15:00 berlin in tokyo").as_deref(),
+        Some("= Sun, 18 Oct 2026 22:00 JST")
+    );
+    assert_eq!(
+        display("time in new york").as_deref(),
+        Some("= Sun, 18 Oct 2026 08:00 EDT")
+    );
+    // Out of range shifts are rejected instead of panicking
+    assert_eq!(display("now + 9223372036854775807 seconds"), None);
+    assert_eq!(display("now + 9223372036854775807 weeks"), None);
+    assert_eq!(display("now - 9223372036854775807 years"), None);
+    assert_eq!(display("2026-12-24"), None);
+    assert_eq!(display("5 m in cm"), None);
+}
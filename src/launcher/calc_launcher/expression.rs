@@ -0,0 +1,623 @@
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::utils::config::{ConfigCalc, ConfigGuard};
+
+/// Values that outlive a single query. In daemon mode, these are kept until sherlock exits.
+static STATE: Lazy<Mutex<CalcState>> = Lazy::new(|| Mutex::new(CalcState::default()));
+
+/// User functions may call each other, so recursion has to be limited
+const MAX_DEPTH: usize = 32;
+
+#[derive(Debug, Default)]
+struct CalcState {
+    ans: Option<f64>,
+    variables: HashMap<String, f64>,
+}
+
+/// Result of a single expression. Nothing is stored until it gets committed, so `ans` and
+/// variables only change once a calculation is executed and not on every keystroke.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evaluation {
+    pub value: f64,
+    pub assign: Option<String>,
+}
+impl Evaluation {
+    pub fn commit(&self) {
+        if let Ok(mut state) = STATE.lock() {
+            state.ans = Some(self.value);
+            if let Some(name) = &self.assign {
+                state.variables.insert(name.clone(), self.value);
+            }
+        }
+    }
+}
+
+/// Evaluates an expression like `x = 2pi * 3!` or `15% of 80`
+pub fn evaluate(input: &str) -> Result<Evaluation, String> {
+    let config = ConfigGuard::read()
+        .map(|c| c.calc.clone())
+        .unwrap_or_default();
+    let state = STATE.lock().map_err(|e| e.to_string())?;
+    evaluate_with(input, &config, &state)
+}
+
+fn evaluate_with(
+    input: &str,
+    config: &ConfigCalc,
+    state: &CalcState,
+) -> Result<Evaluation, String> {
+    let tokens = tokenize(input)?;
+
+    // Assignments look like `name = expression`
+    let (assign, tokens) = match tokens.as_slice() {
+        [Token::Ident(name), Token::Assign, rest @ ..] => {
+            if is_reserved(name) {
+                return Err(format!("Cannot assign to \"{}\"", name));
+            }
+            (Some(name.clone()), rest.to_vec())
+        }
+        _ => (None, tokens),
+    };
+
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        config,
+        state,
+        locals: HashMap::new(),
+        depth: 0,
+    };
+    let value = parser.parse()?;
+    if !value.is_finite() {
+        return Err(String::from("Result is not a finite number"));
+    }
+    Ok(Evaluation { value, assign })
+}
+
+fn is_reserved(name: &str) -> bool {
+    matches!(name, "ans" | "of" | "mod" | "xor") || constant(name).is_some()
+}
+
+fn constant(name: &str) -> Option<f64> {
+    match name {
+        "pi" | "π" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        "tau" | "τ" => Some(std::f64::consts::TAU),
+        "phi" | "φ" => Some(1.618_033_988_749_895),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    Percent,
+    Bang,
+    Amp,
+    Pipe,
+    Tilde,
+    Shl,
+    Shr,
+    LParen,
+    RParen,
+    Comma,
+    Assign,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '0'..='9' | '.' => {
+                let (num, len) = number(&chars[i..])?;
+                i += len;
+                tokens.push(Token::Num(num));
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+                continue;
+            }
+            '*' if next == Some('*') => {
+                i += 1;
+                Token::Caret
+            }
+            '<' if next == Some('<') => {
+                i += 1;
+                Token::Shl
+            }
+            '>' if next == Some('>') => {
+                i += 1;
+                Token::Shr
+            }
+            '+' => Token::Plus,
+            '-' | '−' => Token::Minus,
+            '*' | '×' | '·' => Token::Star,
+            '/' | '÷' => Token::Slash,
+            '^' => Token::Caret,
+            '%' => Token::Percent,
+            '!' => Token::Bang,
+            '&' => Token::Amp,
+            '|' => Token::Pipe,
+            '~' => Token::Tilde,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '=' => Token::Assign,
+            _ => return Err(format!("Unexpected character '{}'", c)),
+        };
+        tokens.push(token);
+        i += 1;
+    }
+    Ok(tokens)
+}
+
+/// Parses a number literal at the start of `chars`. Supports `0x`, `0o` and `0b` prefixes,
+/// `_` as digit separator and exponents like `1.5e3`.
+fn number(chars: &[char]) -> Result<(f64, usize), String> {
+    let radix = match (chars.first(), chars.get(1)) {
+        (Some('0'), Some('x' | 'X')) => Some(16),
+        (Some('0'), Some('o' | 'O')) => Some(8),
+        (Some('0'), Some('b' | 'B')) => Some(2),
+        _ => None,
+    };
+    if let Some(radix) = radix {
+        let len = chars[2..]
+            .iter()
+            .take_while(|c| c.is_digit(radix) || **c == '_')
+            .count();
+        let digits: String = chars[2..2 + len].iter().filter(|c| **c != '_').collect();
+        let value = i64::from_str_radix(&digits, radix)
+            .map_err(|_| String::from("Invalid integer literal"))?;
+        return Ok((value as f64, 2 + len));
+    }
+
+    let mut len = chars
+        .iter()
+        .take_while(|c| c.is_ascii_digit() || **c == '.' || **c == '_')
+        .count();
+    // Only treat `e` as exponent if digits follow, so `2e` still means `2 * e`
+    if let Some('e' | 'E') = chars.get(len) {
+        let sign = matches!(chars.get(len + 1), Some('+' | '-')) as usize;
+        let digits = chars[len + 1 + sign..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits > 0 {
+            len += 1 + sign + digits;
+        }
+    }
+    let literal: String = chars[..len].iter().filter(|c| **c != '_').collect();
+    let value = literal
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid number \"{}\"", literal))?;
+    Ok((value, len))
+}
+
+/// Value of a term and whether it was written as a bare percentage. This is needed
+/// because `80 + 15%` means `80 * 1.15` while `15%` alone means `0.15`.
+#[derive(Clone, Copy)]
+struct Term {
+    value: f64,
+    percent: bool,
+}
+impl From<f64> for Term {
+    fn from(value: f64) -> Self {
+        Self {
+            value,
+            percent: false,
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    config: &'a ConfigCalc,
+    state: &'a CalcState,
+    /// Parameters of the user function currently being evaluated
+    locals: HashMap<String, f64>,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(&mut self) -> Result<f64, String> {
+        if self.tokens.is_empty() {
+            return Err(String::from("Empty expression"));
+        }
+        let value = self.bit_or()?;
+        match self.peek() {
+            None => Ok(value),
+            Some(token) => Err(format!("Unexpected token {:?}", token)),
+        }
+    }
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(name)) if name == keyword)
+    }
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("Expected {:?}, found {:?}", expected, token)),
+            None => Err(format!("Expected {:?}", expected)),
+        }
+    }
+    /// Whether the token can start an operand. Used for implicit multiplication and to
+    /// tell the modulo operator apart from percentages.
+    fn starts_operand(token: Option<&Token>) -> bool {
+        match token {
+            Some(Token::Num(_)) | Some(Token::LParen) => true,
+            Some(Token::Ident(name)) => !matches!(name.as_str(), "of" | "mod" | "xor"),
+            _ => false,
+        }
+    }
+
+    fn bit_or(&mut self) -> Result<f64, String> {
+        let mut value = self.bit_xor()?;
+        while self.peek() == Some(&Token::Pipe) {
+            self.next();
+            value = (integer(value)? | integer(self.bit_xor()?)?) as f64;
+        }
+        Ok(value)
+    }
+    fn bit_xor(&mut self) -> Result<f64, String> {
+        let mut value = self.bit_and()?;
+        while self.is_keyword("xor") {
+            self.next();
+            value = (integer(value)? ^ integer(self.bit_and()?)?) as f64;
+        }
+        Ok(value)
+    }
+    fn bit_and(&mut self) -> Result<f64, String> {
+        let mut value = self.shift()?;
+        while self.peek() == Some(&Token::Amp) {
+            self.next();
+            value = (integer(value)? & integer(self.shift()?)?) as f64;
+        }
+        Ok(value)
+    }
+    fn shift(&mut self) -> Result<f64, String> {
+        let mut value = self.additive()?;
+        loop {
+            let left = match self.peek() {
+                Some(Token::Shl) => true,
+                Some(Token::Shr) => false,
+                _ => return Ok(value),
+            };
+            self.next();
+            let amount = integer(self.additive()?)?;
+            let amount = u32::try_from(amount).map_err(|_| "Invalid shift amount")?;
+            let base = integer(value)?;
+            value = if left {
+                base.checked_shl(amount)
+            } else {
+                base.checked_shr(amount)
+            }
+            .ok_or("Invalid shift amount")? as f64;
+        }
+    }
+    fn additive(&mut self) -> Result<f64, String> {
+        let mut value = self.term()?.value;
+        loop {
+            let sign = match self.peek() {
+                Some(Token::Plus) => 1.0,
+                Some(Token::Minus) => -1.0,
+                _ => return Ok(value),
+            };
+            self.next();
+            let rhs = self.term()?;
+            value += if rhs.percent {
+                sign * value * rhs.value
+            } else {
+                sign * rhs.value
+            };
+        }
+    }
+    fn term(&mut self) -> Result<Term, String> {
+        let mut term = self.unary()?;
+        loop {
+            let value = term.value;
+            term = match self.peek() {
+                Some(Token::Star) => {
+                    self.next();
+                    (value * self.unary()?.value).into()
+                }
+                Some(Token::Slash) => {
+                    self.next();
+                    (value / self.unary()?.value).into()
+                }
+                Some(Token::Percent) => {
+                    self.next();
+                    value.rem_euclid(self.unary()?.value).into()
+                }
+                Some(Token::Ident(name)) if name == "mod" => {
+                    self.next();
+                    value.rem_euclid(self.unary()?.value).into()
+                }
+                // Implicit multiplication: `2pi`, `3(1 + 2)`, `(1 + 2)(3 + 4)`
+                token if Self::starts_operand(token) && !matches!(token, Some(Token::Num(_))) => {
+                    (value * self.unary()?.value).into()
+                }
+                _ => return Ok(term),
+            };
+        }
+    }
+    fn unary(&mut self) -> Result<Term, String> {
+        match self.peek() {
+            Some(Token::Minus) => {
+                self.next();
+                let term = self.unary()?;
+                Ok(Term {
+                    value: -term.value,
+                    percent: term.percent,
+                })
+            }
+            Some(Token::Plus) => {
+                self.next();
+                self.unary()
+            }
+            Some(Token::Tilde) => {
+                self.next();
+                Ok((!integer(self.unary()?.value)? as f64).into())
+            }
+            _ => self.power(),
+        }
+    }
+    fn power(&mut self) -> Result<Term, String> {
+        let base = self.postfix()?;
+        if self.peek() == Some(&Token::Caret) {
+            self.next();
+            // Right associative, and `2^-1` should work
+            let exponent = self.unary()?.value;
+            return Ok(base.value.powf(exponent).into());
+        }
+        Ok(base)
+    }
+    fn postfix(&mut self) -> Result<Term, String> {
+        let mut term: Term = self.primary()?.into();
+        loop {
+            match self.peek() {
+                Some(Token::Bang) => {
+                    self.next();
+                    term = factorial(term.value)?.into();
+                }
+                // A percent sign followed by an operand is the modulo operator
+                Some(Token::Percent) if !Self::starts_operand(self.peek_at(1)) => {
+                    self.next();
+                    if self.is_keyword("of") {
+                        self.next();
+                        let of = self.unary()?.value;
+                        term = (term.value / 100.0 * of).into();
+                    } else {
+                        term = Term {
+                            value: term.value / 100.0,
+                            percent: true,
+                        };
+                    }
+                }
+                _ => return Ok(term),
+            }
+        }
+    }
+    fn primary(&mut self) -> Result<f64, String> {
+        match self.next() {
+            Some(Token::Num(value)) => Ok(value),
+            Some(Token::LParen) => {
+                let value = self.bit_or()?;
+                self.expect(Token::RParen)?;
+                Ok(value)
+            }
+            Some(Token::Ident(name)) => {
+                if self.peek() == Some(&Token::LParen) {
+                    self.next();
+                    let args = self.arguments()?;
+                    self.call(&name, &args)
+                } else {
+                    self.variable(&name)
+                }
+            }
+            Some(token) => Err(format!("Unexpected token {:?}", token)),
+            None => Err(String::from("Unexpected end of expression")),
+        }
+    }
+    fn arguments(&mut self) -> Result<Vec<f64>, String> {
+        let mut args = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.next();
+            return Ok(args);
+        }
+        loop {
+            args.push(self.bit_or()?);
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => return Ok(args),
+                _ => return Err(String::from("Expected ',' or ')'")),
+            }
+        }
+    }
+    fn variable(&self, name: &str) -> Result<f64, String> {
+        if let Some(value) = self.locals.get(name) {
+            return Ok(*value);
+        }
+        if name == "ans" {
+            return self
+                .state
+                .ans
+                .ok_or_else(|| String::from("No previous result"));
+        }
+        self.state
+            .variables
+            .get(name)
+            .or_else(|| self.config.constants.get(name))
+            .copied()
+            .or_else(|| constant(name))
+            .ok_or_else(|| format!("Unknown variable \"{}\"", name))
+    }
+    fn call(&mut self, name: &str, args: &[f64]) -> Result<f64, String> {
+        if let Some(value) = builtin(name, args)? {
+            return Ok(value);
+        }
+
+        let Some((params, body)) = self.config.function(name) else {
+            return Err(format!("Unknown function \"{}\"", name));
+        };
+        if params.len() != args.len() {
+            return Err(format!(
+                "Function \"{}\" takes {} argument(s)",
+                name,
+                params.len()
+            ));
+        }
+        if self.depth >= MAX_DEPTH {
+            return Err(String::from("Maximum recursion depth exceeded"));
+        }
+
+        let mut parser = Parser {
+            tokens: tokenize(body)?,
+            pos: 0,
+            config: self.config,
+            state: self.state,
+            locals: params.into_iter().zip(args.iter().copied()).collect(),
+            depth: self.depth + 1,
+        };
+        parser.parse()
+    }
+}
+
+fn builtin(name: &str, args: &[f64]) -> Result<Option<f64>, String> {
+    let unary = |f: fn(f64) -> f64| match args {
+        [x] => Ok(Some(f(*x))),
+        _ => Err(format!("Function \"{}\" takes 1 argument", name)),
+    };
+    match name {
+        "sqrt" => unary(f64::sqrt),
+        "cbrt" => unary(f64::cbrt),
+        "abs" => unary(f64::abs),
+        "exp" => unary(f64::exp),
+        "ln" => unary(f64::ln),
+        "log" | "log10" => unary(f64::log10),
+        "log2" => unary(f64::log2),
+        "sin" => unary(f64::sin),
+        "cos" => unary(f64::cos),
+        "tan" => unary(f64::tan),
+        "asin" => unary(f64::asin),
+        "acos" => unary(f64::acos),
+        "atan" => unary(f64::atan),
+        "sinh" => unary(f64::sinh),
+        "cosh" => unary(f64::cosh),
+        "tanh" => unary(f64::tanh),
+        "floor" => unary(f64::floor),
+        "ceil" => unary(f64::ceil),
+        "round" => unary(f64::round),
+        "trunc" => unary(f64::trunc),
+        "sign" | "signum" => unary(f64::signum),
+        "rad" => unary(f64::to_radians),
+        "deg" => unary(f64::to_degrees),
+        "fact" => match args {
+            [x] => factorial(*x).map(Some),
+            _ => Err(String::from("Function \"fact\" takes 1 argument")),
+        },
+        "pow" => match args {
+            [x, y] => Ok(Some(x.powf(*y))),
+            _ => Err(String::from("Function \"pow\" takes 2 arguments")),
+        },
+        "atan2" => match args {
+            [y, x] => Ok(Some(y.atan2(*x))),
+            _ => Err(String::from("Function \"atan2\" takes 2 arguments")),
+        },
+        "min" => Ok(args.iter().copied().reduce(f64::min)),
+        "max" => Ok(args.iter().copied().reduce(f64::max)),
+        _ => Ok(None),
+    }
+}
+
+fn integer(value: f64) -> Result<i64, String> {
+    if value.fract() != 0.0 || value.abs() > i64::MAX as f64 {
+        return Err(String::from("Bitwise operations require integers"));
+    }
+    Ok(value as i64)
+}
+
+fn factorial(value: f64) -> Result<f64, String> {
+    if value.fract() != 0.0 || !(0.0..=170.0).contains(&value) {
+        return Err(String::from(
+            "Factorial requires an integer between 0 and 170",
+        ));
+    }
+    Ok((1..=value as u64).map(|i| i as f64).product())
+}
+
+#[test]
+fn test_expressions() {
+    let mut config = ConfigCalc::default();
+    config.constants.insert(String::from("g"), 9.81);
+    config
+        .functions
+        .insert(String::from("hyp(a, b)"), String::from("sqrt(a^2 + b^2)"));
+    let state = CalcState {
+        ans: Some(10.0),
+        variables: HashMap::from([(String::from("x"), 4.0)]),
+    };
+    let eval = |input: &str| evaluate_with(input, &config, &state).map(|e| e.value);
+
+    assert_eq!(eval("1 + 2 * 3"), Ok(7.0));
+    assert_eq!(eval("-2^2"), Ok(-4.0));
+    assert_eq!(eval("2^3^2"), Ok(512.0));
+    assert_eq!(eval("ans / 4"), Ok(2.5));
+    assert_eq!(eval("2x + 1"), Ok(9.0));
+    assert_eq!(eval("2(3 + 4)"), Ok(14.0));
+    assert_eq!(eval("0xff + 0b1010 + 0o17"), Ok(280.0));
+    assert_eq!(eval("0xf0 | 0x0f"), Ok(255.0));
+    assert_eq!(eval("6 & 3 xor 1"), Ok(3.0));
+    assert_eq!(eval("1 << 4"), Ok(16.0));
+    assert_eq!(eval("~0"), Ok(-1.0));
+    assert_eq!(eval("5!"), Ok(120.0));
+    assert_eq!(eval("15% of 80"), Ok(12.0));
+    assert_eq!(eval("80 + 25%"), Ok(100.0));
+    assert_eq!(eval("10 % 4"), Ok(2.0));
+    assert_eq!(eval("50%"), Ok(0.5));
+    assert_eq!(eval("hyp(3, 4)"), Ok(5.0));
+    assert_eq!(eval("2g"), Ok(19.62));
+    assert_eq!(eval("1.5e3"), Ok(1500.0));
+    assert!(eval("1.5 & 1").is_err());
+    assert!(eval("firefox").is_err());
+    assert!(eval("2 3").is_err());
+    assert_eq!(
+        evaluate_with("y = 2 * 3", &config, &state),
+        Ok(Evaluation {
+            value: 6.0,
+            assign: Some(String::from("y"))
+        })
+    );
+}
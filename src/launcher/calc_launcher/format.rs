@@ -0,0 +1,161 @@
+use std::env;
+
+use crate::utils::config::{ConfigGuard, OtherDefaults};
+
+/// Decimal and grouping separators of a locale
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumberFormat {
+    pub decimal: char,
+    pub group: char,
+    pub significant_figures: usize,
+}
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal: '.',
+            group: ',',
+            significant_figures: 6,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Uses `calc.locale` from the config, falling back to `LC_ALL`, `LC_NUMERIC` and `LANG`
+    pub fn from_config() -> Self {
+        let (locale, significant_figures) = ConfigGuard::read()
+            .map_or((None, OtherDefaults::significant_figures()), |c| {
+                (c.calc.locale.clone(), c.calc.significant_figures)
+            });
+        let locale = locale.or_else(|| {
+            ["LC_ALL", "LC_NUMERIC", "LANG"]
+                .into_iter()
+                .filter_map(|var| env::var(var).ok())
+                .find(|value| !value.is_empty())
+        });
+        Self {
+            significant_figures,
+            ..locale.as_deref().map(Self::from_locale).unwrap_or_default()
+        }
+    }
+    /// Accepts tags like `de_DE.UTF-8`, `fr-CH` or `en`
+    pub fn from_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        let (decimal, group) = match language.as_str() {
+            "de" | "es" | "it" | "nl" | "pt" | "da" | "tr" | "id" | "el" | "ro" | "hr" | "sl"
+            | "sr" => (',', '.'),
+            "fr" | "ru" | "pl" | "sv" | "fi" | "nb" | "nn" | "no" | "cs" | "sk" | "uk" | "hu"
+            | "bg" | "lt" | "lv" | "et" => (',', '\u{a0}'),
+            _ => ('.', ','),
+        };
+        Self {
+            decimal,
+            group,
+            ..Default::default()
+        }
+    }
+
+    /// Parses numbers like `1,000.5`, `1.000,5` or `1000,5`. A single separator followed by
+    /// exactly three digits is a group separator unless it is the locale's decimal separator.
+    pub fn parse(&self, input: &str) -> Option<f64> {
+        let input: String = input
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '\u{a0}' && *c != '\'')
+            .collect();
+        let separators: Vec<(usize, char)> = input
+            .char_indices()
+            .filter(|(_, c)| *c == '.' || *c == ',')
+            .collect();
+
+        let decimal = match separators.as_slice() {
+            [] => None,
+            [.., (_, last)] if separators.iter().any(|(_, c)| c != last) => Some(*last),
+            [(i, c)] => {
+                let is_group = *c != self.decimal && input.len() - i - 1 == 3;
+                (!is_group).then_some(*c)
+            }
+            // The same separator multiple times can only be grouping
+            _ => None,
+        };
+
+        let normalized: String = input
+            .chars()
+            .filter_map(|c| match c {
+                '.' | ',' if Some(c) == decimal => Some('.'),
+                '.' | ',' => None,
+                c => Some(c),
+            })
+            .collect();
+        normalized.parse().ok()
+    }
+
+    /// Formats a number using the locale's separators. The integer part is always kept in
+    /// full, fractional digits are only shown up to the configured significant figures.
+    /// `max_decimals` caps the fractional digits, e.g. at two for currencies.
+    pub fn format(&self, value: f64, max_decimals: Option<usize>) -> String {
+        let magnitude = if value == 0.0 {
+            1
+        } else {
+            value.abs().log10().floor() as i64 + 1
+        };
+        let mut decimals = (self.significant_figures as i64 - magnitude).max(0) as usize;
+        if let Some(max) = max_decimals {
+            decimals = decimals.min(max);
+        }
+
+        let fixed = format!("{:.*}", decimals, value.abs());
+        let (int_part, frac_part) = fixed.split_once('.').unwrap_or((&fixed, ""));
+        let frac_part = frac_part.trim_end_matches('0');
+
+        let mut grouped = String::new();
+        for (i, c) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i) % 3 == 0 {
+                grouped.push(self.group);
+            }
+            grouped.push(c);
+        }
+
+        let negative = value < 0.0 && (int_part != "0" || !frac_part.is_empty());
+        let mut out = String::from(if negative { "-" } else { "" });
+        out.push_str(&grouped);
+        if !frac_part.is_empty() {
+            out.push(self.decimal);
+            out.push_str(frac_part);
+        }
+        out
+    }
+}
+
+/// Unformatted representation used for copying. Rounds away floating point noise like
+/// `2.5399999999999996`.
+pub fn raw(value: f64) -> String {
+    format!("{:.12e}", value)
+        .parse::<f64>()
+        .unwrap_or(value)
+        .to_string()
+}
+
+#[test]
+fn test_number_format() {
+    let en = NumberFormat::from_locale("en_US.UTF-8");
+    let de = NumberFormat::from_locale("de_DE.UTF-8");
+
+    assert_eq!(en.parse("1,000,000.5"), Some(1_000_000.5));
+    assert_eq!(en.parse("1,000"), Some(1000.0));
+    assert_eq!(en.parse("1,5"), Some(1.5));
+    assert_eq!(de.parse("1.000.000,5"), Some(1_000_000.5));
+    assert_eq!(de.parse("1,5"), Some(1.5));
+    assert_eq!(de.parse("1.000"), Some(1000.0));
+    assert_eq!(de.parse("1.5"), Some(1.5));
+
+    assert_eq!(en.format(151_234_567.891, None), "151,234,568");
+    assert_eq!(en.format(1.0 / 3.0, None), "0.333333");
+    assert_eq!(en.format(1234.5678, Some(2)), "1,234.57");
+    assert_eq!(de.format(1234.5678, Some(2)), "1.234,57");
+    assert_eq!(en.format(-0.25, None), "-0.25");
+    assert_eq!(en.format(12.0, None), "12");
+    assert_eq!(raw(0.0254 / 0.01), "2.54");
+}
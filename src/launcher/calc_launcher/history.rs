@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    sherlock_error,
+    utils::{
+        errors::{SherlockError, SherlockErrorType},
+        paths,
+    },
+};
+
+/// Older calculations are dropped once the history grows beyond this
+const MAX_ENTRIES: usize = 1000;
+
+/// A single executed calculation
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct HistoryEntry {
+    pub query: String,
+    /// Value that was copied, e.g. `2.54`
+    pub result: String,
+    /// Result as shown on the tile, e.g. `= 2.54 cm`
+    #[serde(default)]
+    pub display: String,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+}
+impl HistoryEntry {
+    pub fn new(query: &str, result: &str, display: &str) -> Self {
+        Self {
+            query: query.trim().to_string(),
+            result: result.to_string(),
+            display: display.to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+/// Calculations are stored as JSON lines in `calc_history.jsonl` of the data directory
+pub fn history_path() -> Result<PathBuf, SherlockError> {
+    Ok(paths::get_data_dir()?.join("calc_history.jsonl"))
+}
+
+pub fn append(entry: HistoryEntry) -> Result<(), SherlockError> {
+    append_to(&history_path()?, entry)
+}
+
+/// Returns all calculations, newest first. Repeated calculations are only listed once.
+pub fn read() -> Vec<HistoryEntry> {
+    history_path()
+        .map(|path| read_from(&path))
+        .unwrap_or_default()
+}
+
+fn append_to(path: &Path, entry: HistoryEntry) -> Result<(), SherlockError> {
+    let mut entries = read_all(path);
+    entries.push(entry);
+    let skip = entries.len().saturating_sub(MAX_ENTRIES);
+    let content: String = entries
+        .iter()
+        .skip(skip)
+        .filter_map(|entry| serde_json::to_string(entry).ok())
+        .map(|line| line + "\n")
+        .collect();
+    fs::write(path, content).map_err(|e| {
+        sherlock_error!(
+            SherlockErrorType::FileWriteError(path.to_path_buf()),
+            e.to_string()
+        )
+    })
+}
+
+fn read_all(path: &Path) -> Vec<HistoryEntry> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+fn read_from(path: &Path) -> Vec<HistoryEntry> {
+    let mut entries = read_all(path);
+    entries.reverse();
+    let mut seen = std::collections::HashSet::new();
+    entries.retain(|entry| seen.insert((entry.query.clone(), entry.result.clone())));
+    entries
+}
+
+#[test]
+fn test_history_file() {
+    let path = std::env::temp_dir().join(format!("sherlock-calc-history-{}", std::process::id()));
+    let _ = fs::remove_file(&path);
+    append_to(&path, HistoryEntry::new("1 + 1", "2", "= 2")).unwrap();
+    append_to(&path, HistoryEntry::new("2 * 3", "6", "= 6")).unwrap();
+    append_to(&path, HistoryEntry::new(" 1 + 1 ", "2", "= 2")).unwrap();
+
+    let entries = read_from(&path);
+    let queries: Vec<&str> = entries.iter().map(|e| e.query.as_str()).collect();
+    assert_eq!(queries, ["1 + 1", "2 * 3"]);
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,130 @@
+pub mod currency;
+pub mod datetime;
+pub mod expression;
+pub mod format;
+pub mod history;
+pub mod programmer;
+pub mod units;
+
+use crate::utils::config::ConfigGuard;
+pub use currency::{Currency, CurrencyProvider, CURRENCIES};
+use format::NumberFormat;
+use regex::Regex;
+use std::collections::HashSet;
+use units::UnitFamily;
+
+#[derive(Clone, Debug)]
+pub struct CalculatorLauncher {
+    pub capabilities: HashSet<String>,
+}
+
+pub struct Calculator;
+impl Calculator {
+    pub fn measurement(keyword: &str, unit_str: &str) -> Option<(String, String)> {
+        let format = NumberFormat::from_config();
+        let full_pattern = r"(?i)(\d[\d.,]*)\s*([a-zµ°²³/]+\d?)\s*(in|to)\s*([a-zµ°²³/]+\d?)";
+        let full_re = Regex::new(full_pattern).unwrap();
+        let (value, from, to) = if let Some(caps) = full_re.captures(keyword) {
+            (
+                format.parse(&caps[1])?,
+                caps[2].to_string(),
+                caps[4].to_string(),
+            )
+        } else {
+            // Support for partial ones
+            let part_pattern = r"(?i)(\d[\d.,]*)\s*([a-zµ°²³/]+\d?)";
+            let part_re = Regex::new(part_pattern).unwrap();
+            let caps = part_re.captures(keyword)?;
+            let config = ConfigGuard::read().ok()?;
+            let to = match unit_str {
+                "currencies" => config.units.currency.clone(),
+                _ => (UnitFamily::get(unit_str)?.default)(&config.units).to_string(),
+            };
+            (format.parse(&caps[1])?, caps[2].to_string(), to)
+        };
+
+        let (factor_from, _, _) = Measurements::match_unit(&from, unit_str)?;
+        let (factor_to, name, plural) = Measurements::match_unit(&to, unit_str)?;
+
+        let base = Calculator::to_basis(factor_from, value);
+        let res = Calculator::to_unit(factor_to, base);
+        let name = if res == 1.0 { name } else { plural };
+        let display = format.format(res, (unit_str == "currencies").then_some(2));
+        Some((format::raw(res), format!("= {} {}", display, name)))
+    }
+    pub fn temperature(keyword: &str) -> Option<(String, String)> {
+        let ctof = |c: f64| (c * 9.0 / 5.0) + 32.0;
+        let ftoc = |f: f64| (f - 32.0) * 5.0 / 9.0;
+        let parse_unit = |unit: &str| {
+            if unit == "c" || unit.len() > 1 && "celsius".contains(&unit) {
+                "C"
+            } else if unit == "f" || unit.len() > 1 && "fahrenheit".contains(&unit) {
+                "F"
+            } else {
+                "C"
+            }
+        };
+        let full_pattern = r"(?i)^(?P<value>\d[\d.,]*)\s*(?:degrees?|°)?\s*(?P<from>(c|f)(elsius|ahrenheit)?)?\s*(?:to|as|in)?\s*(?:degrees?|°)?\s*(?P<to>(c|f)(elsius|ahrenheit)?)?$";
+
+        let full_re = Regex::new(full_pattern).unwrap();
+        match full_re.captures(keyword) {
+            Some(caps) => {
+                let format = NumberFormat::from_config();
+                let value = format.parse(caps.name("value")?.as_str())?;
+                let from = parse_unit(&caps.name("from")?.as_str().to_lowercase());
+                let to = caps
+                    .name("to")
+                    .map_or(if from == "C" { "F" } else { "C" }, |v| {
+                        parse_unit(v.as_str())
+                    });
+                match to {
+                    "C" => {
+                        let res = ftoc(value);
+                        Some((
+                            format::raw(res),
+                            format!("= {} °C", format.format(res, None)),
+                        ))
+                    }
+                    _ => {
+                        let res = ctof(value);
+                        Some((
+                            format::raw(res),
+                            format!("= {} °F", format.format(res, None)),
+                        ))
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+    fn to_basis(factor: f64, value: f64) -> f64 {
+        value * factor
+    }
+    fn to_unit(factor: f64, value: f64) -> f64 {
+        value / factor
+    }
+}
+
+enum Measurements {}
+impl Measurements {
+    /// Returns the factor to the base unit as well as the singular and plural name
+    fn match_unit(unit: &str, unit_str: &str) -> Option<(f64, String, String)> {
+        match unit_str {
+            "currencies" => {
+                if Currency::unit_exists(unit) {
+                    let (rate, symbol) = CURRENCIES
+                        .get()?
+                        .as_ref()
+                        .and_then(|c| c.match_unit(unit))?;
+                    Some((rate, symbol.clone(), symbol))
+                } else {
+                    None
+                }
+            }
+            _ => {
+                let unit = UnitFamily::get(unit_str)?.find(unit)?;
+                Some((unit.factor, unit.name.to_string(), unit.plural.to_string()))
+            }
+        }
+    }
+}
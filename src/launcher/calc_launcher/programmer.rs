@@ -0,0 +1,331 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static INT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?P<int>-?(?:0x[0-9a-f_]+|0o[0-7_]+|0b[01_]+|[0-9a-f_]+))(?:\s*(?P<base>hex|hexadecimal|dec|decimal|oct|octal|bin|binary))?(?:\s+(?:in|to|as)\s+(?P<target>[a-z0-9+\- ]+))?$",
+    )
+    .unwrap()
+});
+static WIDTH_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:(?P<sign>[iu])(?P<int>\d+)|(?P<bits>\d+)\s*-?\s*bits?)$").unwrap()
+});
+static CODEPOINT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?i)u\+([0-9a-f]{1,6})$").unwrap());
+static PERMISSION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[r-][w-][xsS-][r-][w-][xsS-][r-][w-][xtT-]$").unwrap());
+
+/// Result of a programmer conversion. Every representation gets its own copy action.
+#[derive(Debug, PartialEq)]
+pub struct Conversion {
+    /// Representation copied when the tile is executed
+    pub copy: String,
+    pub display: String,
+    /// Pairs of label and value
+    pub representations: Vec<(String, String)>,
+}
+impl Conversion {
+    fn new(representations: Vec<(&str, String)>, primary: usize) -> Option<Self> {
+        let copy = representations.get(primary)?.1.clone();
+        let display = representations
+            .iter()
+            .map(|(_, value)| value.as_str())
+            .collect::<Vec<_>>()
+            .join("  ·  ");
+        Some(Self {
+            copy,
+            display: format!("= {}", display),
+            representations: representations
+                .into_iter()
+                .map(|(label, value)| (label.to_string(), value))
+                .collect(),
+        })
+    }
+}
+
+/// Converts integer literals between bases, bit widths and byte orders and handles
+/// codepoints like `'A'` or `U+1F600` and unix permissions like `rwxr-xr-x`.
+/// Plain decimal numbers without a target are left to the math parser.
+pub fn convert(keyword: &str) -> Option<Conversion> {
+    let keyword = keyword.trim();
+    if let Some(c) = keyword
+        .strip_prefix('\'')
+        .and_then(|k| k.strip_suffix('\''))
+        .filter(|c| c.chars().count() == 1)
+        .and_then(|c| c.chars().next())
+    {
+        return codepoint(c as u32, false);
+    }
+    if let Some(caps) = CODEPOINT_RE.captures(keyword) {
+        return codepoint(u32::from_str_radix(&caps[1], 16).ok()?, false);
+    }
+    if PERMISSION_RE.is_match(keyword) {
+        return permission(parse_permission(keyword)?);
+    }
+
+    let keyword = keyword.to_lowercase();
+    let caps = INT_RE.captures(&keyword)?;
+    let literal = &caps["int"];
+    let base = caps.name("base").map(|b| b.as_str());
+    let target = caps.name("target").map(|t| t.as_str().trim());
+    let explicit = base.is_some() || has_prefix(literal);
+
+    match target {
+        None if explicit => bases(parse_int(literal, base)?, None),
+        None => None,
+        Some("hex" | "hexadecimal") => bases(parse_int(literal, base)?, Some(0)),
+        Some("dec" | "decimal") => bases(parse_int(literal, base)?, Some(1)),
+        Some("oct" | "octal") => bases(parse_int(literal, base)?, Some(2)),
+        Some("bin" | "binary") => bases(parse_int(literal, base)?, Some(3)),
+        // Permissions are octal even without a prefix
+        Some("perm" | "perms" | "permission" | "permissions" | "chmod" | "mode") => {
+            let value = parse_int(literal, base.or((!explicit).then_some("oct")))?;
+            permission(u32::try_from(value).ok()?)
+        }
+        Some("char" | "codepoint" | "unicode") => {
+            codepoint(u32::try_from(parse_int(literal, base)?).ok()?, true)
+        }
+        Some(order @ ("bytes" | "le" | "be")) => bytes(parse_int(literal, base)?, order == "be"),
+        Some(target) => {
+            let width = WIDTH_RE.captures(target)?;
+            let bits: u32 = width
+                .name("int")
+                .or(width.name("bits"))?
+                .as_str()
+                .parse()
+                .ok()?;
+            let signed = width.name("sign").is_none_or(|s| s.as_str() == "i");
+            twos_complement(parse_int(literal, base)?, bits, signed)
+        }
+    }
+}
+
+fn has_prefix(literal: &str) -> bool {
+    let literal = literal.trim_start_matches('-');
+    ["0x", "0o", "0b"].iter().any(|p| literal.starts_with(p))
+}
+
+fn parse_int(literal: &str, base: Option<&str>) -> Option<i128> {
+    let (negative, literal) = match literal.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, literal),
+    };
+    let literal = literal.replace('_', "");
+    let (radix, digits) = if let Some(digits) = literal.strip_prefix("0x") {
+        (16, digits)
+    } else if let Some(digits) = literal.strip_prefix("0o") {
+        (8, digits)
+    } else if let Some(digits) = literal.strip_prefix("0b") {
+        (2, digits)
+    } else {
+        let radix = match base {
+            Some("hex" | "hexadecimal") => 16,
+            Some("oct" | "octal") => 8,
+            Some("bin" | "binary") => 2,
+            _ => 10,
+        };
+        (radix, literal.as_str())
+    };
+    let value = i128::from_str_radix(digits, radix).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+fn signed(prefix: &str, value: i128, digits: String) -> String {
+    let sign = if value < 0 { "-" } else { "" };
+    format!("{}{}{}", sign, prefix, digits)
+}
+
+/// Hexadecimal, decimal, octal and binary. `primary` selects the copied representation,
+/// defaulting to decimal.
+fn bases(value: i128, primary: Option<usize>) -> Option<Conversion> {
+    let abs = value.unsigned_abs();
+    Conversion::new(
+        vec![
+            ("Hexadecimal", signed("0x", value, format!("{:x}", abs))),
+            ("Decimal", value.to_string()),
+            ("Octal", signed("0o", value, format!("{:o}", abs))),
+            ("Binary", signed("0b", value, format!("{:b}", abs))),
+        ],
+        primary.unwrap_or(1),
+    )
+}
+
+/// Interprets the value as a `bits` wide integer, e.g. `0xff as i8` is `-1`
+fn twos_complement(value: i128, bits: u32, is_signed: bool) -> Option<Conversion> {
+    if !(1..=64).contains(&bits) {
+        return None;
+    }
+    let min = -(1i128 << (bits - 1));
+    let max = (1i128 << bits) - 1;
+    if value < min || value > max {
+        return None;
+    }
+    let raw = (value as u128) & ((1u128 << bits) - 1);
+    let as_signed = if (raw >> (bits - 1)) & 1 == 1 {
+        raw as i128 - (1i128 << bits)
+    } else {
+        raw as i128
+    };
+    let hex_digits = bits.div_ceil(4) as usize;
+    Conversion::new(
+        vec![
+            ("Hexadecimal", format!("0x{:0w$x}", raw, w = hex_digits)),
+            ("Binary", format!("0b{:0w$b}", raw, w = bits as usize)),
+            ("Signed", as_signed.to_string()),
+            ("Unsigned", raw.to_string()),
+        ],
+        if is_signed { 2 } else { 3 },
+    )
+}
+
+/// Byte strings in little and big endian using the smallest fitting integer size
+fn bytes(value: i128, big_endian: bool) -> Option<Conversion> {
+    let size = [1u32, 2, 4, 8].into_iter().find(|size| {
+        let bits = size * 8;
+        value >= -(1i128 << (bits - 1)) && value < (1i128 << bits)
+    })? as usize;
+    let le = value.to_le_bytes();
+    let le: Vec<String> = le[..size].iter().map(|b| format!("{:02x}", b)).collect();
+    let be: Vec<String> = le.iter().rev().cloned().collect();
+    Conversion::new(
+        vec![
+            ("Little Endian", le.join(" ")),
+            ("Big Endian", be.join(" ")),
+            (
+                "Escaped",
+                le.iter().map(|b| format!("\\x{}", b)).collect::<String>(),
+            ),
+        ],
+        if big_endian { 1 } else { 0 },
+    )
+}
+
+/// Copies the decimal value unless the character itself was asked for
+fn codepoint(value: u32, to_char: bool) -> Option<Conversion> {
+    let c = char::from_u32(value)?;
+    let mut buf = [0; 4];
+    let utf8: Vec<String> = c
+        .encode_utf8(&mut buf)
+        .bytes()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    let display = if c.is_control() {
+        c.escape_unicode().to_string()
+    } else {
+        c.to_string()
+    };
+    Conversion::new(
+        vec![
+            ("Character", display),
+            ("Decimal", value.to_string()),
+            ("Codepoint", format!("U+{:04X}", value)),
+            ("UTF-8", utf8.join(" ")),
+        ],
+        match (c.is_control(), to_char) {
+            (true, _) => 2,
+            (false, true) => 0,
+            (false, false) => 1,
+        },
+    )
+}
+
+/// Symbols for read, write and execute of user, group and others
+const PERMISSION_BITS: [(u32, char); 9] = [
+    (0o400, 'r'),
+    (0o200, 'w'),
+    (0o100, 'x'),
+    (0o040, 'r'),
+    (0o020, 'w'),
+    (0o010, 'x'),
+    (0o004, 'r'),
+    (0o002, 'w'),
+    (0o001, 'x'),
+];
+/// Setuid, setgid and sticky bit with the index of the execute symbol they replace
+const SPECIAL_BITS: [(u32, usize, char); 3] =
+    [(0o4000, 2, 's'), (0o2000, 5, 's'), (0o1000, 8, 't')];
+
+fn parse_permission(symbolic: &str) -> Option<u32> {
+    let mut mode = 0;
+    for (i, c) in symbolic.chars().enumerate() {
+        let (bit, symbol) = PERMISSION_BITS.get(i)?;
+        let special = SPECIAL_BITS.iter().find(|(_, index, _)| *index == i);
+        match (c, special) {
+            ('-', _) => {}
+            (c, _) if c == *symbol => mode |= bit,
+            (c, Some((special, _, s))) if c == *s => mode |= bit | special,
+            (c, Some((special, _, s))) if c == s.to_ascii_uppercase() => mode |= special,
+            _ => return None,
+        }
+    }
+    Some(mode)
+}
+
+fn permission(mode: u32) -> Option<Conversion> {
+    if mode > 0o7777 {
+        return None;
+    }
+    let mut symbolic: Vec<char> = PERMISSION_BITS
+        .iter()
+        .map(|(bit, symbol)| if mode & bit != 0 { *symbol } else { '-' })
+        .collect();
+    for (special, index, symbol) in SPECIAL_BITS {
+        if mode & special != 0 {
+            symbolic[index] = if symbolic[index] == 'x' {
+                symbol
+            } else {
+                symbol.to_ascii_uppercase()
+            };
+        }
+    }
+    let class = |shift: u32| -> String {
+        PERMISSION_BITS[..3]
+            .iter()
+            .filter(|(bit, _)| (mode >> shift) & (bit >> 6) != 0)
+            .map(|(_, symbol)| *symbol)
+            .collect()
+    };
+    let chmod = format!("u={},g={},o={}", class(6), class(3), class(0));
+    let octal = if mode > 0o777 {
+        format!("{:04o}", mode)
+    } else {
+        format!("{:03o}", mode)
+    };
+    Conversion::new(
+        vec![
+            ("Symbolic", symbolic.into_iter().collect()),
+            ("Octal", octal),
+            ("chmod", chmod),
+        ],
+        0,
+    )
+}
+
+#[test]
+fn test_programmer_conversions() {
+    let copy = |query: &str| convert(query).map(|c| c.copy);
+    let values = |query: &str| {
+        convert(query)
+            .map(|c| {
+                c.representations
+                    .into_iter()
+                    .map(|(_, v)| v)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+
+    assert_eq!(values("0xff"), ["0xff", "255", "0o377", "0b11111111"]);
+    assert_eq!(copy("0xff to bin").as_deref(), Some("0b11111111"));
+    assert_eq!(copy("ff hex").as_deref(), Some("255"));
+    assert_eq!(convert("255"), None);
+    assert_eq!(copy("0xff as i8").as_deref(), Some("-1"));
+    assert_eq!(values("-1 as u16")[0], "0xffff");
+    assert_eq!(convert("256 as u8"), None);
+    assert_eq!(copy("1000 to bytes").as_deref(), Some("e8 03"));
+    assert_eq!(copy("'A'").as_deref(), Some("65"));
+    assert_eq!(values("U+1F600")[3], "f0 9f 98 80");
+    assert_eq!(copy("755 to perm").as_deref(), Some("rwxr-xr-x"));
+    assert_eq!(copy("0o4755 to chmod").as_deref(), Some("rwsr-xr-x"));
+    assert_eq!(values("rw-r--r--")[1], "644");
+    assert_eq!(values("rwxr-xr-t")[1], "1755");
+}
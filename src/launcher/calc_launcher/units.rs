@@ -0,0 +1,593 @@
+use crate::utils::config::ConfigUnits;
+
+/// A single unit. `factor` converts a value of this unit into the family's base unit.
+#[derive(Debug)]
+pub struct Unit {
+    pub factor: f64,
+    pub name: &'static str,
+    pub plural: &'static str,
+    /// Case-sensitive aliases are checked first, so `Mb` (megabit) and `MB` (megabyte)
+    /// can coexist. Afterwards, aliases are compared case-insensitively.
+    pub aliases: &'static [&'static str],
+}
+
+/// A family of units that can be converted into each other
+#[derive(Debug)]
+pub struct UnitFamily {
+    pub name: &'static str,
+    pub capability: &'static str,
+    /// Unit to convert into if the query does not name one
+    pub default: fn(&ConfigUnits) -> &str,
+    pub units: &'static [Unit],
+}
+impl UnitFamily {
+    pub fn get(name: &str) -> Option<&'static UnitFamily> {
+        FAMILIES.iter().find(|f| f.name == name)
+    }
+    pub fn find(&self, alias: &str) -> Option<&'static Unit> {
+        let units = self.units;
+        units
+            .iter()
+            .find(|u| u.aliases.contains(&alias))
+            .or_else(|| {
+                units
+                    .iter()
+                    .find(|u| u.aliases.iter().any(|a| a.eq_ignore_ascii_case(alias)))
+            })
+    }
+}
+
+macro_rules! unit {
+    ($factor:expr, $name:literal, $plural:literal, [$($alias:literal),+ $(,)?]) => {
+        Unit {
+            factor: $factor,
+            name: $name,
+            plural: $plural,
+            aliases: &[$($alias),+],
+        }
+    };
+}
+
+/// All unit families except temperatures and currencies, which are not simple factors.
+/// Families are tried in this order.
+pub static FAMILIES: &[UnitFamily] = &[
+    UnitFamily {
+        name: "lengths",
+        capability: "calc.lengths",
+        default: |u| &u.lengths,
+        units: &[
+            unit!(
+                1000.0,
+                "Kilometer",
+                "Kilometers",
+                ["km", "kilometer", "kilometers", "kilos"]
+            ),
+            unit!(1.0, "Meter", "Meters", ["m", "meter", "meters"]),
+            unit!(
+                0.1,
+                "Decimeter",
+                "Decimeters",
+                ["dm", "decimeter", "decimeters"]
+            ),
+            unit!(
+                0.01,
+                "Centimeter",
+                "Centimeters",
+                ["cm", "centimeter", "centimeters", "cents"]
+            ),
+            unit!(
+                0.001,
+                "Millimeter",
+                "Millimeters",
+                ["mm", "millimeter", "millimeters"]
+            ),
+            unit!(
+                1e-6,
+                "Micrometer",
+                "Micrometers",
+                ["um", "µm", "micrometer", "micrometers"]
+            ),
+            unit!(
+                1e-9,
+                "Nanometer",
+                "Nanometers",
+                ["nm", "nanometer", "nanometers"]
+            ),
+            unit!(0.0254, "Inch", "Inches", ["in", "inch", "inches"]),
+            unit!(0.3048, "Foot", "Feet", ["ft", "foot", "feet"]),
+            unit!(0.9144, "Yard", "Yards", ["yd", "yard", "yards"]),
+            unit!(1609.344, "Mile", "Miles", ["mi", "mile", "miles"]),
+            unit!(1852.0, "Nautical Mile", "Nautical Miles", ["nmi"]),
+        ],
+    },
+    UnitFamily {
+        name: "weights",
+        capability: "calc.weights",
+        default: |u| &u.weights,
+        units: &[
+            unit!(
+                1000.0,
+                "Tonne",
+                "Tonnes",
+                ["t", "tonne", "tonnes", "ton", "tons"]
+            ),
+            unit!(
+                1.0,
+                "Kilogram",
+                "Kilograms",
+                ["kg", "kilogram", "kilograms"]
+            ),
+            unit!(0.001, "Gram", "Grams", ["g", "gram", "grams"]),
+            unit!(
+                1e-6,
+                "Milligram",
+                "Milligrams",
+                ["mg", "milligram", "milligrams"]
+            ),
+            unit!(
+                0.45359237,
+                "Pound",
+                "Pounds",
+                ["lb", "lbs", "pound", "pounds"]
+            ),
+            unit!(0.028349523125, "Ounce", "Ounces", ["oz", "ounce", "ounces"]),
+            unit!(6.35029318, "Stone", "Stones", ["st", "stone", "stones"]),
+            unit!(
+                0.015,
+                "Tablespoon",
+                "Tablespoons",
+                ["tbsp", "tablespoon", "tablespoons"]
+            ),
+            unit!(
+                0.005,
+                "Teaspoon",
+                "Teaspoons",
+                ["tsp", "teaspoon", "teaspoons"]
+            ),
+            unit!(0.00036, "Pinch", "Pinches", ["pinch", "pinches"]),
+            unit!(0.0006, "Dash", "Dashes", ["dash", "dashes"]),
+        ],
+    },
+    UnitFamily {
+        name: "volumes",
+        capability: "calc.volumes",
+        default: |u| &u.volumes,
+        units: &[
+            unit!(
+                1.0,
+                "Liter",
+                "Liters",
+                ["l", "liter", "liters", "litre", "litres"]
+            ),
+            unit!(
+                0.1,
+                "Deciliter",
+                "Deciliters",
+                ["dl", "deciliter", "deciliters"]
+            ),
+            unit!(
+                0.01,
+                "Centiliter",
+                "Centiliters",
+                ["cl", "centiliter", "centiliters"]
+            ),
+            unit!(
+                0.001,
+                "Milliliter",
+                "Milliliters",
+                ["ml", "milliliter", "milliliters"]
+            ),
+            unit!(
+                1000.0,
+                "Cubic Meter",
+                "Cubic Meters",
+                ["m3", "m³", "cubicmeter", "cubicmeters"]
+            ),
+            unit!(
+                3.785411784,
+                "Gallon",
+                "Gallons",
+                ["gal", "gallon", "gallons"]
+            ),
+            unit!(0.946352946, "Quart", "Quarts", ["qt", "quart", "quarts"]),
+            unit!(0.473176473, "Pint", "Pints", ["pt", "pint", "pints"]),
+            unit!(0.24, "Cup", "Cups", ["cup", "cups"]),
+            unit!(
+                0.0295735295625,
+                "Fluid Ounce",
+                "Fluid Ounces",
+                ["oz", "floz", "fluidounce", "fluidounces"]
+            ),
+        ],
+    },
+    UnitFamily {
+        name: "areas",
+        capability: "calc.areas",
+        default: |u| &u.areas,
+        units: &[
+            unit!(
+                1e6,
+                "Square Kilometer",
+                "Square Kilometers",
+                ["km2", "km²", "sqkm"]
+            ),
+            unit!(1.0, "Square Meter", "Square Meters", ["m2", "m²", "sqm"]),
+            unit!(
+                1e-4,
+                "Square Centimeter",
+                "Square Centimeters",
+                ["cm2", "cm²"]
+            ),
+            unit!(
+                1e-6,
+                "Square Millimeter",
+                "Square Millimeters",
+                ["mm2", "mm²"]
+            ),
+            unit!(1e4, "Hectare", "Hectares", ["ha", "hectare", "hectares"]),
+            unit!(100.0, "Are", "Ares", ["are", "ares"]),
+            unit!(4046.8564224, "Acre", "Acres", ["ac", "acre", "acres"]),
+            unit!(
+                2_589_988.110336,
+                "Square Mile",
+                "Square Miles",
+                ["mi2", "mi²", "sqmi"]
+            ),
+            unit!(
+                0.83612736,
+                "Square Yard",
+                "Square Yards",
+                ["yd2", "yd²", "sqyd"]
+            ),
+            unit!(
+                0.09290304,
+                "Square Foot",
+                "Square Feet",
+                ["ft2", "ft²", "sqft"]
+            ),
+            unit!(
+                0.00064516,
+                "Square Inch",
+                "Square Inches",
+                ["in2", "in²", "sqin"]
+            ),
+        ],
+    },
+    UnitFamily {
+        name: "speeds",
+        capability: "calc.speeds",
+        default: |u| &u.speeds,
+        units: &[
+            unit!(1.0, "Meter per Second", "Meters per Second", ["m/s", "mps"]),
+            unit!(
+                1.0 / 3.6,
+                "Kilometer per Hour",
+                "Kilometers per Hour",
+                ["km/h", "kmh", "kph"]
+            ),
+            unit!(0.44704, "Mile per Hour", "Miles per Hour", ["mph", "mi/h"]),
+            unit!(
+                0.3048,
+                "Foot per Second",
+                "Feet per Second",
+                ["ft/s", "fps"]
+            ),
+            unit!(
+                1852.0 / 3600.0,
+                "Knot",
+                "Knots",
+                ["kn", "kt", "knot", "knots"]
+            ),
+            unit!(343.0, "Mach", "Mach", ["mach"]),
+        ],
+    },
+    UnitFamily {
+        name: "data",
+        capability: "calc.data",
+        default: |u| &u.data,
+        units: &[
+            unit!(0.125, "Bit", "Bits", ["b", "bit", "bits"]),
+            unit!(1.0, "Byte", "Bytes", ["B", "byte", "bytes"]),
+            // SI prefixes
+            unit!(
+                1e3,
+                "Kilobyte",
+                "Kilobytes",
+                ["kB", "KB", "kb", "kilobyte", "kilobytes"]
+            ),
+            unit!(
+                1e6,
+                "Megabyte",
+                "Megabytes",
+                ["MB", "mb", "megabyte", "megabytes"]
+            ),
+            unit!(
+                1e9,
+                "Gigabyte",
+                "Gigabytes",
+                ["GB", "gb", "gigabyte", "gigabytes"]
+            ),
+            unit!(
+                1e12,
+                "Terabyte",
+                "Terabytes",
+                ["TB", "tb", "terabyte", "terabytes"]
+            ),
+            unit!(
+                1e15,
+                "Petabyte",
+                "Petabytes",
+                ["PB", "pb", "petabyte", "petabytes"]
+            ),
+            unit!(
+                125.0,
+                "Kilobit",
+                "Kilobits",
+                ["Kb", "kbit", "kilobit", "kilobits"]
+            ),
+            unit!(
+                125e3,
+                "Megabit",
+                "Megabits",
+                ["Mb", "mbit", "megabit", "megabits"]
+            ),
+            unit!(
+                125e6,
+                "Gigabit",
+                "Gigabits",
+                ["Gb", "gbit", "gigabit", "gigabits"]
+            ),
+            unit!(
+                125e9,
+                "Terabit",
+                "Terabits",
+                ["Tb", "tbit", "terabit", "terabits"]
+            ),
+            // IEC prefixes
+            unit!(
+                1024.0,
+                "Kibibyte",
+                "Kibibytes",
+                ["KiB", "kib", "kibibyte", "kibibytes"]
+            ),
+            unit!(
+                1048576.0,
+                "Mebibyte",
+                "Mebibytes",
+                ["MiB", "mib", "mebibyte", "mebibytes"]
+            ),
+            unit!(
+                1073741824.0,
+                "Gibibyte",
+                "Gibibytes",
+                ["GiB", "gib", "gibibyte", "gibibytes"]
+            ),
+            unit!(
+                1099511627776.0,
+                "Tebibyte",
+                "Tebibytes",
+                ["TiB", "tib", "tebibyte", "tebibytes"]
+            ),
+            unit!(
+                1125899906842624.0,
+                "Pebibyte",
+                "Pebibytes",
+                ["PiB", "pib", "pebibyte", "pebibytes"]
+            ),
+        ],
+    },
+    UnitFamily {
+        name: "durations",
+        capability: "calc.durations",
+        default: |u| &u.durations,
+        units: &[
+            unit!(
+                1e-9,
+                "Nanosecond",
+                "Nanoseconds",
+                ["ns", "nanosecond", "nanoseconds"]
+            ),
+            unit!(
+                1e-6,
+                "Microsecond",
+                "Microseconds",
+                ["us", "µs", "microsecond", "microseconds"]
+            ),
+            unit!(
+                1e-3,
+                "Millisecond",
+                "Milliseconds",
+                ["ms", "millisecond", "milliseconds"]
+            ),
+            unit!(
+                1.0,
+                "Second",
+                "Seconds",
+                ["s", "sec", "secs", "second", "seconds"]
+            ),
+            unit!(
+                60.0,
+                "Minute",
+                "Minutes",
+                ["min", "mins", "minute", "minutes"]
+            ),
+            unit!(3600.0, "Hour", "Hours", ["h", "hr", "hrs", "hour", "hours"]),
+            unit!(86400.0, "Day", "Days", ["d", "day", "days"]),
+            unit!(604800.0, "Week", "Weeks", ["wk", "week", "weeks"]),
+            unit!(2629746.0, "Month", "Months", ["mo", "month", "months"]),
+            unit!(31556952.0, "Year", "Years", ["y", "yr", "year", "years"]),
+        ],
+    },
+    UnitFamily {
+        name: "pressures",
+        capability: "calc.pressures",
+        default: |u| &u.pressures,
+        units: &[
+            unit!(1.0, "Pascal", "Pascals", ["pa", "pascal", "pascals"]),
+            unit!(
+                100.0,
+                "Hectopascal",
+                "Hectopascals",
+                ["hpa", "hectopascal", "hectopascals"]
+            ),
+            unit!(
+                1e3,
+                "Kilopascal",
+                "Kilopascals",
+                ["kpa", "kilopascal", "kilopascals"]
+            ),
+            unit!(
+                1e6,
+                "Megapascal",
+                "Megapascals",
+                ["mpa", "megapascal", "megapascals"]
+            ),
+            unit!(1e5, "Bar", "Bar", ["bar"]),
+            unit!(100.0, "Millibar", "Millibar", ["mbar", "millibar"]),
+            unit!(
+                101325.0,
+                "Atmosphere",
+                "Atmospheres",
+                ["atm", "atmosphere", "atmospheres"]
+            ),
+            unit!(6894.757293168, "PSI", "PSI", ["psi"]),
+            unit!(
+                133.322387415,
+                "Millimeter of Mercury",
+                "Millimeters of Mercury",
+                ["mmhg"]
+            ),
+            unit!(101325.0 / 760.0, "Torr", "Torr", ["torr"]),
+        ],
+    },
+    UnitFamily {
+        name: "energies",
+        capability: "calc.energies",
+        default: |u| &u.energies,
+        units: &[
+            unit!(1.0, "Joule", "Joules", ["j", "joule", "joules"]),
+            unit!(
+                1e3,
+                "Kilojoule",
+                "Kilojoules",
+                ["kj", "kilojoule", "kilojoules"]
+            ),
+            unit!(
+                1e6,
+                "Megajoule",
+                "Megajoules",
+                ["MJ", "mj", "megajoule", "megajoules"]
+            ),
+            unit!(4.184, "Calorie", "Calories", ["cal", "calorie", "calories"]),
+            unit!(
+                4184.0,
+                "Kilocalorie",
+                "Kilocalories",
+                ["kcal", "kilocalorie", "kilocalories"]
+            ),
+            unit!(3600.0, "Watt Hour", "Watt Hours", ["wh"]),
+            unit!(3.6e6, "Kilowatt Hour", "Kilowatt Hours", ["kwh"]),
+            unit!(
+                1.602176634e-19,
+                "Electronvolt",
+                "Electronvolts",
+                ["ev", "electronvolt", "electronvolts"]
+            ),
+            unit!(1055.05585262, "BTU", "BTU", ["btu"]),
+        ],
+    },
+    UnitFamily {
+        name: "powers",
+        capability: "calc.powers",
+        default: |u| &u.powers,
+        units: &[
+            unit!(1.0, "Watt", "Watts", ["w", "watt", "watts"]),
+            unit!(
+                1e3,
+                "Kilowatt",
+                "Kilowatts",
+                ["kw", "kilowatt", "kilowatts"]
+            ),
+            unit!(
+                1e6,
+                "Megawatt",
+                "Megawatts",
+                ["MW", "megawatt", "megawatts"]
+            ),
+            unit!(
+                1e-3,
+                "Milliwatt",
+                "Milliwatts",
+                ["mW", "milliwatt", "milliwatts"]
+            ),
+            unit!(
+                745.69987158227,
+                "Horsepower",
+                "Horsepower",
+                ["hp", "horsepower"]
+            ),
+            unit!(735.49875, "Metric Horsepower", "Metric Horsepower", ["ps"]),
+        ],
+    },
+    UnitFamily {
+        name: "angles",
+        capability: "calc.angles",
+        default: |u| &u.angles,
+        units: &[
+            unit!(1.0, "Degree", "Degrees", ["deg", "°", "degree", "degrees"]),
+            unit!(
+                180.0 / std::f64::consts::PI,
+                "Radian",
+                "Radians",
+                ["rad", "radian", "radians"]
+            ),
+            unit!(
+                0.9,
+                "Gradian",
+                "Gradians",
+                ["grad", "gon", "gradian", "gradians"]
+            ),
+            unit!(360.0, "Turn", "Turns", ["turn", "turns", "rev"]),
+            unit!(
+                1.0 / 60.0,
+                "Arcminute",
+                "Arcminutes",
+                ["arcmin", "arcminute", "arcminutes"]
+            ),
+            unit!(
+                1.0 / 3600.0,
+                "Arcsecond",
+                "Arcseconds",
+                ["arcsec", "arcsecond", "arcseconds"]
+            ),
+        ],
+    },
+];
+
+#[test]
+fn test_unit_lookup() {
+    let data = UnitFamily::get("data").unwrap();
+    assert_eq!(data.find("MB").map(|u| u.name), Some("Megabyte"));
+    assert_eq!(data.find("Mb").map(|u| u.name), Some("Megabit"));
+    assert_eq!(data.find("mb").map(|u| u.name), Some("Megabyte"));
+    assert_eq!(data.find("MEGABIT").map(|u| u.name), Some("Megabit"));
+    assert_eq!(data.find("MiB").map(|u| u.factor), Some(1048576.0));
+
+    // Aliases must be unique within a family, otherwise lookups are ambiguous
+    for family in FAMILIES {
+        let aliases: Vec<&str> = family
+            .units
+            .iter()
+            .flat_map(|u| u.aliases)
+            .copied()
+            .collect();
+        for (i, alias) in aliases.iter().enumerate() {
+            assert!(
+                !aliases[i + 1..].contains(alias),
+                "duplicate alias {} in {}",
+                alias,
+                family.name
+            );
+        }
+    }
+}
@@ -1,7 +0,0 @@
-use std::collections::HashSet;
-
-#[derive(Clone, Debug)]
-pub struct ClipboardLauncher {
-    pub clipboard_content: String,
-    pub capabilities: HashSet<String>,
-}
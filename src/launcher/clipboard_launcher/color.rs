@@ -0,0 +1,713 @@
+use std::collections::HashSet;
+
+use crate::loader::launcher_loader::COLOR_RE;
+
+/// Longer clipboard contents are not checked for colors
+const MAX_LENGTH: usize = 64;
+
+/// Lightness steps in percent used for the lighter and darker shades of a palette
+const SHADES: [f64; 2] = [15.0, 30.0];
+
+/// A color in sRGB with all channels ranging from 0 to 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Color {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub alpha: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Hex,
+    Rgb,
+    Hsl,
+    Hsv,
+    Oklch,
+    Cmyk,
+    Named,
+}
+impl Format {
+    const ALL: [Format; 7] = [
+        Format::Hex,
+        Format::Rgb,
+        Format::Hsl,
+        Format::Hsv,
+        Format::Oklch,
+        Format::Cmyk,
+        Format::Named,
+    ];
+    pub fn capability(&self) -> &'static str {
+        match self {
+            Self::Hex => "colors.hex",
+            Self::Rgb => "colors.rgb",
+            Self::Hsl => "colors.hsl",
+            Self::Hsv => "colors.hsv",
+            Self::Oklch => "colors.oklch",
+            Self::Cmyk => "colors.cmyk",
+            Self::Named => "colors.named",
+        }
+    }
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Hex => "HEX",
+            Self::Rgb => "RGB",
+            Self::Hsl => "HSL",
+            Self::Hsv => "HSV",
+            Self::Oklch => "OKLCH",
+            Self::Cmyk => "CMYK",
+            Self::Named => "Named",
+        }
+    }
+}
+
+/// A single copyable color tile
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorTile {
+    pub title: String,
+    pub category: String,
+    /// Value copied on activation
+    pub value: String,
+    /// Colors shown side by side in the icon
+    pub swatch: Vec<[u8; 3]>,
+}
+impl ColorTile {
+    fn new(title: String, category: &str, swatch: Vec<[u8; 3]>) -> Self {
+        Self {
+            value: title.clone(),
+            title,
+            category: category.to_string(),
+            swatch,
+        }
+    }
+}
+
+/// Returns the tiles for the clipboard content, starting with the color as copied.
+/// Two colors separated by `on` yield their WCAG contrast ratio instead.
+pub fn tiles(content: &str, capabilities: &HashSet<String>) -> Vec<ColorTile> {
+    let content = content.trim();
+    if content.len() > MAX_LENGTH {
+        return vec![];
+    }
+    let all = capabilities.contains("colors.all");
+    let formats: Vec<Format> = Format::ALL
+        .into_iter()
+        .filter(|f| all || capabilities.contains(f.capability()))
+        .collect();
+
+    if all || capabilities.contains("colors.contrast") {
+        let pair = content
+            .to_lowercase()
+            .split_once(" on ")
+            .map(|(fg, bg)| (parse(fg, &formats), parse(bg, &formats)));
+        if let Some((Some((fg, _)), Some((bg, _)))) = pair {
+            return vec![contrast_tile(fg, bg)];
+        }
+    }
+
+    let Some((color, input)) = parse(content, &formats) else {
+        return vec![];
+    };
+    let mut tiles = vec![ColorTile::new(
+        content.to_string(),
+        "From Clipboard",
+        vec![color.to_rgb8()],
+    )];
+    tiles.extend(formats.iter().filter(|f| **f != input).map(|format| {
+        let category = match format {
+            Format::Named if color.exact_name().is_none() => "Nearest Named",
+            _ => format.label(),
+        };
+        ColorTile::new(color.format(*format), category, vec![color.to_rgb8()])
+    }));
+
+    if capabilities.contains("colors.palette") {
+        // Names would mostly be approximations, so the palette falls back to hex
+        let output = match input {
+            Format::Named => Format::Hex,
+            other => other,
+        };
+        let shade = |dl: f64, category: &str| {
+            let shade = color.adjust(0.0, dl);
+            ColorTile::new(shade.format(output), category, vec![shade.to_rgb8()])
+        };
+        tiles.extend(SHADES.iter().map(|dl| shade(*dl, "Lighter")));
+        tiles.extend(SHADES.iter().map(|dl| shade(-dl, "Darker")));
+        for (dh, category) in [
+            (180.0, "Complementary"),
+            (150.0, "Split Complementary"),
+            (210.0, "Split Complementary"),
+        ] {
+            let complement = color.adjust(dh, 0.0);
+            tiles.push(ColorTile::new(
+                complement.format(output),
+                category,
+                vec![color.to_rgb8(), complement.to_rgb8()],
+            ));
+        }
+    }
+    tiles
+}
+
+fn contrast_tile(fg: Color, bg: Color) -> ColorTile {
+    let ratio = contrast(fg, bg);
+    let rating = match ratio {
+        r if r >= 7.0 => "AAA",
+        r if r >= 4.5 => "AA · AAA Large",
+        r if r >= 3.0 => "AA Large",
+        _ => "Insufficient",
+    };
+    let value = format!("{}:1", trim(ratio, 2));
+    ColorTile {
+        title: format!("Contrast {}", value),
+        category: format!("WCAG {}", rating),
+        value,
+        swatch: vec![bg.to_rgb8(), fg.to_rgb8(), bg.to_rgb8()],
+    }
+}
+
+/// WCAG 2 contrast ratio between two colors, ranging from 1 to 21
+pub fn contrast(a: Color, b: Color) -> f64 {
+    let (a, b) = (a.luminance(), b.luminance());
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Parses a color in any of the given formats
+pub fn parse(input: &str, formats: &[Format]) -> Option<(Color, Format)> {
+    let input = input.trim().to_lowercase();
+    if formats.contains(&Format::Named) {
+        if let Some(color) = Color::from_name(&input) {
+            return Some((color, Format::Named));
+        }
+    }
+    let caps = COLOR_RE.captures(&input)?;
+    let (color, format) = if let Some(hex) = caps.name("hex") {
+        (Color::from_hex(hex.as_str())?, Format::Hex)
+    } else {
+        let args = caps.name("args").or(caps.name("bare"))?.as_str();
+        let values = components(args);
+        let format = match caps.name("func").map(|f| f.as_str()) {
+            Some("rgb" | "rgba") => Format::Rgb,
+            Some("hsl" | "hsla") => Format::Hsl,
+            Some("hsv" | "hsb") => Format::Hsv,
+            Some("oklch") => Format::Oklch,
+            Some("cmyk") => Format::Cmyk,
+            // Without a prefix, percentages indicate hsl
+            _ if args.contains('%') => Format::Hsl,
+            _ => Format::Rgb,
+        };
+        (Color::from_components(format, &values)?, format)
+    };
+    formats.contains(&format).then_some((color, format))
+}
+
+/// Splits color arguments like `255, 0, 0` or `62.8% 0.25 29 / 50%` into their values.
+/// The flag tells whether the value was given in percent.
+fn components(args: &str) -> Vec<(f64, bool)> {
+    args.split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map_while(|s| {
+            let percent = s.ends_with('%');
+            let number = s.trim_end_matches('%').trim_end_matches("deg");
+            number.parse::<f64>().ok().map(|n| (n, percent))
+        })
+        .collect()
+}
+
+impl Color {
+    fn new(r: f64, g: f64, b: f64) -> Self {
+        Self {
+            r: r.clamp(0.0, 1.0),
+            g: g.clamp(0.0, 1.0),
+            b: b.clamp(0.0, 1.0),
+            alpha: 1.0,
+        }
+    }
+    fn with_alpha(mut self, alpha: Option<&(f64, bool)>) -> Self {
+        if let Some((a, percent)) = alpha {
+            self.alpha = if *percent { a / 100.0 } else { *a }.clamp(0.0, 1.0);
+        }
+        self
+    }
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim_start_matches('#');
+        // Short forms like `#fff` repeat every digit
+        let hex: String = match hex.len() {
+            3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+            6 | 8 => hex.to_string(),
+            _ => return None,
+        };
+        let channel = |i: usize| {
+            u8::from_str_radix(hex.get(i..i + 2)?, 16)
+                .ok()
+                .map(|c| c as f64 / 255.0)
+        };
+        let mut color = Self::new(channel(0)?, channel(2)?, channel(4)?);
+        if hex.len() == 8 {
+            color.alpha = channel(6)?;
+        }
+        Some(color)
+    }
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.replace([' ', '-'], "");
+        NAMED_COLORS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, hex)| Self::from_u32(*hex))
+    }
+    fn from_u32(hex: u32) -> Self {
+        let channel = |shift: u32| ((hex >> shift) & 0xff) as f64 / 255.0;
+        Self::new(channel(16), channel(8), channel(0))
+    }
+    fn from_components(format: Format, values: &[(f64, bool)]) -> Option<Self> {
+        // Saturation and friends are percentages whether or not they carry a `%`
+        let percent = |i: usize| values.get(i).map(|(v, _)| v / 100.0);
+        let color = match format {
+            Format::Rgb => {
+                let channel = |i: usize| {
+                    values
+                        .get(i)
+                        .map(|(v, p)| if *p { v / 100.0 } else { v / 255.0 })
+                };
+                Self::new(channel(0)?, channel(1)?, channel(2)?).with_alpha(values.get(3))
+            }
+            Format::Hsl => Self::from_hsl(values.first()?.0, percent(1)?, percent(2)?)
+                .with_alpha(values.get(3)),
+            Format::Hsv => Self::from_hsv(values.first()?.0, percent(1)?, percent(2)?),
+            Format::Oklch => {
+                let (l, l_percent) = *values.first()?;
+                let (c, c_percent) = *values.get(1)?;
+                let l = if l_percent { l / 100.0 } else { l };
+                // 100% chroma corresponds to 0.4
+                let c = if c_percent { c / 100.0 * 0.4 } else { c };
+                Self::from_oklch(l, c, values.get(2)?.0).with_alpha(values.get(3))
+            }
+            Format::Cmyk => {
+                let k = percent(3)?;
+                let channel = |i: usize| percent(i).map(|v| (1.0 - v) * (1.0 - k));
+                Self::new(channel(0)?, channel(1)?, channel(2)?)
+            }
+            Format::Hex | Format::Named => return None,
+        };
+        Some(color)
+    }
+    fn from_hsv(h: f64, s: f64, v: f64) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let s = s.clamp(0.0, 1.0);
+        let v = v.clamp(0.0, 1.0);
+        let channel = |n: f64| {
+            let k = (n + h) % 6.0;
+            v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
+        };
+        Self::new(channel(5.0), channel(3.0), channel(1.0))
+    }
+    fn from_hsl(h: f64, s: f64, l: f64) -> Self {
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        let v = l + s * l.min(1.0 - l);
+        let s = if v == 0.0 { 0.0 } else { 2.0 * (1.0 - l / v) };
+        Self::from_hsv(h, s, v)
+    }
+    fn from_oklch(l: f64, c: f64, h: f64) -> Self {
+        let (a, b) = (c * h.to_radians().cos(), c * h.to_radians().sin());
+        let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+        let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+        let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+        Self::new(
+            to_srgb(4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_),
+            to_srgb(-1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_),
+            to_srgb(-0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_),
+        )
+    }
+
+    pub fn to_rgb8(self) -> [u8; 3] {
+        let channel = |c: f64| (c * 255.0).round() as u8;
+        [channel(self.r), channel(self.g), channel(self.b)]
+    }
+    fn to_hsv(self) -> (f64, f64, f64) {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let delta = max - min;
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == self.r {
+            60.0 * ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max == self.g {
+            60.0 * ((self.b - self.r) / delta + 2.0)
+        } else {
+            60.0 * ((self.r - self.g) / delta + 4.0)
+        };
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+        (h, s, max)
+    }
+    fn to_hsl(self) -> (f64, f64, f64) {
+        let (h, s, v) = self.to_hsv();
+        let l = v * (1.0 - s / 2.0);
+        let s = if l == 0.0 || l == 1.0 {
+            0.0
+        } else {
+            (v - l) / l.min(1.0 - l)
+        };
+        (h, s, l)
+    }
+    fn to_oklab(self) -> (f64, f64, f64) {
+        let (r, g, b) = (to_linear(self.r), to_linear(self.g), to_linear(self.b));
+        let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+        let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+        let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+        (
+            0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+            1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+            0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+        )
+    }
+    fn to_oklch(self) -> (f64, f64, f64) {
+        let (l, a, b) = self.to_oklab();
+        let c = (a * a + b * b).sqrt();
+        // The hue of grays is meaningless
+        let h = if c < 1e-4 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        };
+        (l, c, h)
+    }
+    /// Relative luminance as defined by WCAG 2
+    pub fn luminance(self) -> f64 {
+        0.2126 * to_linear(self.r) + 0.7152 * to_linear(self.g) + 0.0722 * to_linear(self.b)
+    }
+
+    pub fn exact_name(self) -> Option<&'static str> {
+        let rgb = self.to_rgb8();
+        NAMED_COLORS
+            .iter()
+            .find(|(_, hex)| Self::from_u32(*hex).to_rgb8() == rgb)
+            .map(|(name, _)| *name)
+    }
+    /// Closest named color by its distance in OKLab
+    fn nearest_name(self) -> &'static str {
+        let (l, a, b) = self.to_oklab();
+        let distance = |hex: u32| {
+            let (l2, a2, b2) = Self::from_u32(hex).to_oklab();
+            (l - l2).powi(2) + (a - a2).powi(2) + (b - b2).powi(2)
+        };
+        NAMED_COLORS
+            .iter()
+            .min_by(|x, y| distance(x.1).total_cmp(&distance(y.1)))
+            .map_or("black", |(name, _)| name)
+    }
+
+    /// Rotates the hue by `dh` degrees and changes the lightness by `dl` percent
+    pub fn adjust(self, dh: f64, dl: f64) -> Self {
+        let (h, s, l) = self.to_hsl();
+        Self {
+            alpha: self.alpha,
+            ..Self::from_hsl(h + dh, s, l + dl / 100.0)
+        }
+    }
+
+    pub fn format(self, format: Format) -> String {
+        let alpha = (self.alpha < 1.0).then(|| trim(self.alpha, 2));
+        match format {
+            Format::Hex => {
+                let [r, g, b] = self.to_rgb8();
+                let mut hex = format!("#{:02x}{:02x}{:02x}", r, g, b);
+                if self.alpha < 1.0 {
+                    hex.push_str(&format!("{:02x}", (self.alpha * 255.0).round() as u8));
+                }
+                hex
+            }
+            Format::Rgb => {
+                let [r, g, b] = self.to_rgb8();
+                match alpha {
+                    Some(a) => format!("rgba({}, {}, {}, {})", r, g, b, a),
+                    None => format!("rgb({}, {}, {})", r, g, b),
+                }
+            }
+            Format::Hsl => {
+                let (h, s, l) = self.to_hsl();
+                let (h, s, l) = (trim(h, 1), trim(s * 100.0, 1), trim(l * 100.0, 1));
+                match alpha {
+                    Some(a) => format!("hsla({}, {}%, {}%, {})", h, s, l, a),
+                    None => format!("hsl({}, {}%, {}%)", h, s, l),
+                }
+            }
+            Format::Hsv => {
+                let (h, s, v) = self.to_hsv();
+                format!(
+                    "hsv({}, {}%, {}%)",
+                    trim(h, 1),
+                    trim(s * 100.0, 1),
+                    trim(v * 100.0, 1)
+                )
+            }
+            Format::Oklch => {
+                let (l, c, h) = self.to_oklch();
+                let lch = format!("{}% {} {}", trim(l * 100.0, 1), trim(c, 3), trim(h, 1));
+                match alpha {
+                    Some(a) => format!("oklch({} / {})", lch, a),
+                    None => format!("oklch({})", lch),
+                }
+            }
+            Format::Cmyk => {
+                let k = 1.0 - self.r.max(self.g).max(self.b);
+                let channel = |c: f64| {
+                    if k >= 1.0 {
+                        String::from("0")
+                    } else {
+                        trim((1.0 - c - k) / (1.0 - k) * 100.0, 1)
+                    }
+                };
+                format!(
+                    "cmyk({}%, {}%, {}%, {}%)",
+                    channel(self.r),
+                    channel(self.g),
+                    channel(self.b),
+                    trim(k * 100.0, 1)
+                )
+            }
+            Format::Named => self
+                .exact_name()
+                .unwrap_or_else(|| self.nearest_name())
+                .to_string(),
+        }
+    }
+}
+
+fn to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+fn to_srgb(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Rounds to the given number of decimals and drops trailing zeros
+fn trim(value: f64, decimals: usize) -> String {
+    let s = format!("{:.*}", decimals, value);
+    let s = if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &s
+    };
+    match s {
+        "-0" => String::from("0"),
+        s => s.to_string(),
+    }
+}
+
+/// CSS named colors. Aliases like `aqua` and `grey` come after their counterpart,
+/// so lookups by value return the common name.
+const NAMED_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("cyan", 0x00ffff),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgrey", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("magenta", 0xff00ff),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("grey", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgrey", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32),
+];
+
+#[test]
+fn test_color_conversions() {
+    let all = [Format::Hex, Format::Rgb, Format::Hsl, Format::Named];
+    let (red, format) = parse("#f00", &all).unwrap();
+    assert_eq!(format, Format::Hex);
+    assert_eq!(red.format(Format::Rgb), "rgb(255, 0, 0)");
+    assert_eq!(red.format(Format::Hsl), "hsl(0, 100%, 50%)");
+    assert_eq!(red.format(Format::Hsv), "hsv(0, 100%, 100%)");
+    assert_eq!(red.format(Format::Cmyk), "cmyk(0%, 100%, 100%, 0%)");
+    assert_eq!(red.format(Format::Oklch), "oklch(62.8% 0.258 29.2)");
+    assert_eq!(red.format(Format::Named), "red");
+
+    let (color, format) = parse("hsl(120, 50%, 50%)", &all).unwrap();
+    assert_eq!(format, Format::Hsl);
+    assert_eq!(color.format(Format::Hex), "#40bf40");
+    assert_eq!(
+        parse("Rebecca Purple", &all).unwrap().0.format(Format::Hex),
+        "#663399"
+    );
+    assert!(parse("rgb(1, 2, 3)", &[Format::Hex]).is_none());
+
+    // Conversions back from the other formats
+    let (oklch, _) = parse("oklch(62.8% 0.258 29.2)", &[Format::Oklch]).unwrap();
+    assert_eq!(oklch.to_rgb8(), [255, 0, 0]);
+    let (cmyk, _) = parse("cmyk(0, 100, 100, 0)", &[Format::Cmyk]).unwrap();
+    assert_eq!(cmyk.to_rgb8(), [255, 0, 0]);
+    let (hsv, _) = parse("hsv(240, 100%, 50%)", &[Format::Hsv]).unwrap();
+    assert_eq!(hsv.format(Format::Named), "navy");
+
+    let white = Color::from_hex("fff").unwrap();
+    let gray = Color::from_hex("777").unwrap();
+    assert_eq!(trim(contrast(white, gray), 2), "4.48");
+
+    let capabilities = HashSet::from([String::from("colors.all")]);
+    let tiles = tiles("#fff on #777", &capabilities);
+    assert_eq!(tiles[0].value, "4.48:1");
+    assert_eq!(tiles[0].category, "WCAG AA Large");
+}
+
+#[test]
+fn test_color_palette() {
+    let capabilities = HashSet::from([String::from("colors.hex"), String::from("colors.palette")]);
+    let shades = tiles("#808080", &capabilities);
+    let values: Vec<&str> = shades.iter().map(|t| t.value.as_str()).collect();
+    assert_eq!(
+        values[..5],
+        ["#808080", "#a6a6a6", "#cdcdcd", "#5a5a5a", "#343434"]
+    );
+
+    let complement = tiles("#ff0000", &capabilities)
+        .into_iter()
+        .find(|t| t.category == "Complementary")
+        .unwrap();
+    assert_eq!(complement.value, "#00ffff");
+}
@@ -0,0 +1,13 @@
+use std::collections::HashSet;
+
+pub mod color;
+
+use color::ColorTile;
+
+#[derive(Clone, Debug)]
+pub struct ClipboardLauncher {
+    pub clipboard_content: String,
+    pub capabilities: HashSet<String>,
+    /// Color tiles generated from the clipboard content. Each one is shown as its own tile.
+    pub colors: Vec<ColorTile>,
+}
@@ -0,0 +1,378 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    sherlock_error,
+    utils::errors::{SherlockError, SherlockErrorType},
+};
+
+const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!@#$%^&*()-_=+[]{};:,.<>/?~";
+
+const LOREM_START: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit.";
+const LOREM_WORDS: [&str; 64] = [
+    "lorem",
+    "ipsum",
+    "dolor",
+    "sit",
+    "amet",
+    "consectetur",
+    "adipiscing",
+    "elit",
+    "sed",
+    "do",
+    "eiusmod",
+    "tempor",
+    "incididunt",
+    "ut",
+    "labore",
+    "et",
+    "dolore",
+    "magna",
+    "aliqua",
+    "enim",
+    "ad",
+    "minim",
+    "veniam",
+    "quis",
+    "nostrud",
+    "exercitation",
+    "ullamco",
+    "laboris",
+    "nisi",
+    "aliquip",
+    "ex",
+    "ea",
+    "commodo",
+    "consequat",
+    "duis",
+    "aute",
+    "irure",
+    "in",
+    "reprehenderit",
+    "voluptate",
+    "velit",
+    "esse",
+    "cillum",
+    "eu",
+    "fugiat",
+    "nulla",
+    "pariatur",
+    "excepteur",
+    "sint",
+    "occaecat",
+    "cupidatat",
+    "non",
+    "proident",
+    "sunt",
+    "culpa",
+    "qui",
+    "officia",
+    "deserunt",
+    "mollit",
+    "anim",
+    "id",
+    "est",
+    "laborum",
+    "vitae",
+];
+
+static GENERATOR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?ix)^\s*(?:
+            (?P<uuid>uuid|guid)\s*(?:v?(?P<version>[47]))? |
+            (?P<pw>pw|pass|password)(?:\s+(?P<length>\d+))?(?P<nosym>\s+nosym)? |
+            (?P<lorem>lorem|ipsum)(?:\s+(?P<count>\d+))?(?:\s+(?P<unit>words?|sentences?|paragraphs?))? |
+            (?:random|rand)(?:\s+(?P<min>-?\d+)(?:\s*(?:-|\.\.|to)\s*(?P<max>-?\d+))?)?
+        )\s*$",
+    )
+    .unwrap()
+});
+
+#[derive(Clone, Debug)]
+pub struct GenerateLauncher {
+    /// Length of passwords if the query does not specify one
+    pub password_length: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoremUnit {
+    Words,
+    Sentences,
+    Paragraphs,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Generator {
+    Uuid { version: u8 },
+    Password { length: usize, symbols: bool },
+    Lorem { count: usize, unit: LoremUnit },
+    Random { min: i64, max: i64 },
+}
+impl Generator {
+    pub fn parse(keyword: &str, password_length: usize) -> Option<Self> {
+        let caps = GENERATOR_RE.captures(keyword)?;
+        let number = |name: &str| caps.name(name).and_then(|m| m.as_str().parse::<i64>().ok());
+        let generator = if caps.name("uuid").is_some() {
+            let version = number("version").unwrap_or(4) as u8;
+            Self::Uuid { version }
+        } else if caps.name("pw").is_some() {
+            let length = number("length").map_or(password_length, |l| l as usize);
+            Self::Password {
+                length: length.clamp(4, 256),
+                symbols: caps.name("nosym").is_none(),
+            }
+        } else if caps.name("lorem").is_some() {
+            let unit = match caps.name("unit").map(|u| u.as_str().to_lowercase()) {
+                Some(u) if u.starts_with('w') => LoremUnit::Words,
+                Some(u) if u.starts_with('s') => LoremUnit::Sentences,
+                _ => LoremUnit::Paragraphs,
+            };
+            let count = number("count").unwrap_or(1).clamp(1, 100) as usize;
+            Self::Lorem { count, unit }
+        } else {
+            // `random 6` picks from 1 to 6
+            let (min, max) = match (number("min"), number("max")) {
+                (Some(min), Some(max)) => (min.min(max), min.max(max)),
+                (Some(max), None) => (1.min(max), 1.max(max)),
+                _ => (1, 100),
+            };
+            Self::Random { min, max }
+        };
+        Some(generator)
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Self::Uuid { version } => format!("UUID v{}", version),
+            Self::Password { length, symbols } => {
+                let charset = charset(*symbols).chars().count() as f64;
+                format!(
+                    "Password with {} characters{} · {} bits",
+                    length,
+                    if *symbols { "" } else { " without symbols" },
+                    (*length as f64 * charset.log2()).floor()
+                )
+            }
+            Self::Lorem { count, unit } => {
+                let unit = match unit {
+                    LoremUnit::Words => "word",
+                    LoremUnit::Sentences => "sentence",
+                    LoremUnit::Paragraphs => "paragraph",
+                };
+                let plural = if *count == 1 { "" } else { "s" };
+                format!("Lorem ipsum · {} {}{}", count, unit, plural)
+            }
+            Self::Random { min, max } => format!("Random number from {} to {}", min, max),
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Self::Password { .. } => "dialog-password",
+            Self::Lorem { .. } => "format-justify-fill",
+            Self::Uuid { .. } | Self::Random { .. } => "view-refresh",
+        }
+    }
+
+    /// Produces a new value. Every call draws fresh randomness from the operating system.
+    pub fn generate(&self) -> Result<String, SherlockError> {
+        match *self {
+            Self::Uuid { version } => uuid(version),
+            Self::Password { length, symbols } => password(length, symbols),
+            Self::Lorem { count, unit } => lorem(count, unit),
+            Self::Random { min, max } => {
+                let span = max.abs_diff(min);
+                let offset = match span.checked_add(1) {
+                    Some(range) => random_below(range)?,
+                    None => random_u64()?,
+                };
+                Ok(min.wrapping_add_unsigned(offset).to_string())
+            }
+        }
+    }
+}
+
+fn fill_random(buf: &mut [u8]) -> Result<(), SherlockError> {
+    getrandom::fill(buf)
+        .map_err(|e| sherlock_error!(SherlockErrorType::RandomSourceError, e.to_string()))
+}
+
+fn random_u64() -> Result<u64, SherlockError> {
+    let mut buf = [0u8; 8];
+    fill_random(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+/// Uniform number in `0..bound`, using rejection sampling to avoid modulo bias
+fn random_below(bound: u64) -> Result<u64, SherlockError> {
+    let zone = u64::MAX - u64::MAX % bound;
+    loop {
+        let value = random_u64()?;
+        if value < zone {
+            return Ok(value % bound);
+        }
+    }
+}
+
+fn uuid(version: u8) -> Result<String, SherlockError> {
+    let mut bytes = [0u8; 16];
+    fill_random(&mut bytes)?;
+    if version == 7 {
+        // The first 48 bits hold the unix timestamp in milliseconds
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        bytes[..6].copy_from_slice(&millis.to_be_bytes()[2..]);
+    }
+    bytes[6] = (bytes[6] & 0x0f) | (version << 4);
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+fn charset(symbols: bool) -> String {
+    let mut charset = format!("{}{}{}", LOWERCASE, UPPERCASE, DIGITS);
+    if symbols {
+        charset.push_str(SYMBOLS);
+    }
+    charset
+}
+
+/// Draws passwords until one contains every character class
+fn password(length: usize, symbols: bool) -> Result<String, SherlockError> {
+    let charset: Vec<char> = charset(symbols).chars().collect();
+    let mut classes = vec![LOWERCASE, UPPERCASE, DIGITS];
+    if symbols {
+        classes.push(SYMBOLS);
+    }
+    loop {
+        let password = (0..length)
+            .map(|_| random_below(charset.len() as u64).map(|i| charset[i as usize]))
+            .collect::<Result<String, SherlockError>>()?;
+        if classes
+            .iter()
+            .all(|class| password.chars().any(|c| class.contains(c)))
+        {
+            return Ok(password);
+        }
+    }
+}
+
+fn lorem(count: usize, unit: LoremUnit) -> Result<String, SherlockError> {
+    let word = || random_below(LOREM_WORDS.len() as u64).map(|i| LOREM_WORDS[i as usize]);
+    let sentence = || -> Result<String, SherlockError> {
+        let length = 6 + random_below(10)? as usize;
+        let words = (0..length)
+            .map(|_| word())
+            .collect::<Result<Vec<_>, _>>()?
+            .join(" ");
+        let mut chars = words.chars();
+        let first = chars.next().map(|c| c.to_ascii_uppercase());
+        Ok(first.into_iter().chain(chars).chain(['.']).collect())
+    };
+    let paragraph = |first: bool| -> Result<String, SherlockError> {
+        let length = 4 + random_below(4)? as usize;
+        let mut sentences = (0..length)
+            .map(|_| sentence())
+            .collect::<Result<Vec<_>, _>>()?;
+        if first {
+            sentences[0] = LOREM_START.to_string();
+        }
+        Ok(sentences.join(" "))
+    };
+    match unit {
+        LoremUnit::Words => {
+            let mut words: Vec<&str> = LOREM_START
+                .trim_end_matches('.')
+                .split(' ')
+                .take(count)
+                .collect();
+            while words.len() < count {
+                words.push(word()?);
+            }
+            Ok(words.join(" ").replace(',', ""))
+        }
+        LoremUnit::Sentences => {
+            let mut sentences = vec![LOREM_START.to_string()];
+            for _ in 1..count {
+                sentences.push(sentence()?);
+            }
+            Ok(sentences.join(" "))
+        }
+        LoremUnit::Paragraphs => Ok((0..count)
+            .map(|i| paragraph(i == 0))
+            .collect::<Result<Vec<_>, _>>()?
+            .join("\n\n")),
+    }
+}
+
+#[test]
+fn test_generators() {
+    let parse = |keyword: &str| Generator::parse(keyword, 20);
+    assert_eq!(parse("uuid"), Some(Generator::Uuid { version: 4 }));
+    assert_eq!(parse("uuid v7"), Some(Generator::Uuid { version: 7 }));
+    assert_eq!(
+        parse("pw 24 nosym"),
+        Some(Generator::Password {
+            length: 24,
+            symbols: false
+        })
+    );
+    assert_eq!(
+        parse("pw"),
+        Some(Generator::Password {
+            length: 20,
+            symbols: true
+        })
+    );
+    assert_eq!(
+        parse("lorem 3"),
+        Some(Generator::Lorem {
+            count: 3,
+            unit: LoremUnit::Paragraphs
+        })
+    );
+    assert_eq!(
+        parse("random 1-100"),
+        Some(Generator::Random { min: 1, max: 100 })
+    );
+    assert_eq!(
+        parse("random -5 to 5"),
+        Some(Generator::Random { min: -5, max: 5 })
+    );
+    assert_eq!(parse("uuid please"), None);
+    assert_eq!(parse("pwd"), None);
+
+    let uuid = parse("uuid v7").unwrap().generate().unwrap();
+    let uuid_re =
+        Regex::new(r"^[0-9a-f]{8}-[0-9a-f]{4}-7[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$");
+    assert!(uuid_re.unwrap().is_match(&uuid));
+
+    let first = password(24, false).unwrap();
+    assert_eq!(first.len(), 24);
+    assert!(first.chars().all(|c| c.is_ascii_alphanumeric()));
+    assert_ne!(first, password(24, false).unwrap());
+
+    for _ in 0..100 {
+        let value: i64 = Generator::Random { min: -2, max: 2 }
+            .generate()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((-2..=2).contains(&value));
+    }
+    let words = lorem(12, LoremUnit::Words).unwrap();
+    assert!(words.starts_with("Lorem ipsum dolor sit amet consectetur"));
+    assert_eq!(words.split(' ').count(), 12);
+}
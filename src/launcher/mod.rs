@@ -4,17 +4,25 @@ pub mod app_launcher;
 pub mod audio_launcher;
 pub mod bookmark_launcher;
 pub mod bulk_text_launcher;
+pub mod calc_history_launcher;
 pub mod calc_launcher;
 pub mod category_launcher;
 pub mod clipboard_launcher;
 pub mod emoji_picker;
 pub mod event_launcher;
 pub mod file_launcher;
+pub mod generate_launcher;
+pub mod most_used_launcher;
 pub mod pipe_launcher;
+pub mod plugin_launcher;
 pub mod pomodoro_launcher;
 pub mod process_launcher;
+pub mod recent_files_launcher;
+pub mod script_launcher;
+pub mod stdio_worker;
 pub mod system_cmd_launcher;
 pub mod theme_picker;
+pub mod transform_launcher;
 pub mod utils;
 pub mod weather_launcher;
 pub mod web_launcher;
@@ -32,29 +40,37 @@ use crate::{
     ui::tiles::{
         api_tile::ApiTileHandler, app_tile::AppTileHandler, calc_tile::CalcTileHandler,
         clipboard_tile::ClipboardHandler, event_tile::EventTileHandler,
+        generate_tile::GenerateTileHandler, most_used_tile::MostUsedTileHandler,
         mpris_tile::MusicTileHandler, pipe_tile::PipeTileHandler,
-        pomodoro_tile::PomodoroTileHandler, weather_tile::WeatherTileHandler,
-        web_tile::WebTileHandler,
+        pomodoro_tile::PomodoroTileHandler, transform_tile::TransformTileHandler,
+        weather_tile::WeatherTileHandler, web_tile::WebTileHandler,
     },
 };
 
 use app_launcher::AppLauncher;
 use audio_launcher::MusicPlayerLauncher;
 use bookmark_launcher::BookmarkLauncher;
-use bulk_text_launcher::{AsyncCommandResponse, BulkTextLauncher};
+use bulk_text_launcher::{AsyncCommandOutput, AsyncCommandResponse, BulkTextLauncher};
+use calc_history_launcher::CalcHistoryLauncher;
 use calc_launcher::CalculatorLauncher;
 use category_launcher::CategoryLauncher;
 use clipboard_launcher::ClipboardLauncher;
 use emoji_picker::EmojiPicker;
 use event_launcher::EventLauncher;
 use file_launcher::FileLauncher;
+use generate_launcher::GenerateLauncher;
 use gio::glib::property::PropertySet;
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
+use most_used_launcher::MostUsedLauncher;
+use plugin_launcher::PluginLauncher;
 use pomodoro_launcher::Pomodoro;
 use process_launcher::ProcessLauncher;
+use recent_files_launcher::RecentFilesLauncher;
+use script_launcher::ScriptLauncher;
 use simd_json::prelude::ArrayTrait;
 use system_cmd_launcher::CommandLauncher;
 use theme_picker::ThemePicker;
+use transform_launcher::TransformLauncher;
 use utils::HomeType;
 use weather_launcher::{WeatherData, WeatherLauncher};
 use web_launcher::WebLauncher;
@@ -65,17 +81,24 @@ pub enum LauncherType {
     Bookmark(BookmarkLauncher),
     Api(BulkTextLauncher),
     Calc(CalculatorLauncher),
+    CalcHistory(CalcHistoryLauncher),
     Category(CategoryLauncher),
     Clipboard(ClipboardLauncher),
     Command(CommandLauncher),
     Emoji(EmojiPicker),
     Event(EventLauncher),
     File(FileLauncher),
+    Generate(GenerateLauncher),
+    MostUsed(MostUsedLauncher),
     MusicPlayer(MusicPlayerLauncher),
     Pomodoro(Pomodoro),
     Process(ProcessLauncher),
     Pipe(PipeLauncher),
+    Plugin(PluginLauncher),
+    RecentFiles(RecentFilesLauncher),
+    Script(ScriptLauncher),
     Theme(ThemePicker),
+    Transform(TransformLauncher),
     Weather(WeatherLauncher),
     Web(WebLauncher),
     Empty,
@@ -145,7 +168,12 @@ impl Launcher {
             exit: raw.exit,
             next_content: raw.next_content,
             priority: raw.priority as u32,
-            r#async: raw.r#async,
+            // Plugins and scripts can only deliver results asynchronously
+            r#async: raw.r#async
+                || matches!(
+                    launcher_type,
+                    LauncherType::Plugin(_) | LauncherType::Script(_)
+                ),
             home: raw.home,
             launcher_type,
             shortcut: raw.shortcut,
@@ -157,6 +185,7 @@ impl Launcher {
     }
     pub fn from_piped_element(piped: PipedElements, method: String) -> Self {
         let launcher_type = LauncherType::Pipe(PipeLauncher {
+            id: piped.id,
             binary: piped.binary,
             description: piped.description,
             hidden: piped.hidden,
@@ -184,6 +213,73 @@ impl Launcher {
             binds: None,
         }
     }
+    /// Creates a launcher with the same settings but different children, e.g. for apps that
+    /// were installed while Sherlock is running.
+    pub fn with_type(&self, launcher_type: LauncherType) -> Self {
+        Self {
+            name: self.name.clone(),
+            icon: self.icon.clone(),
+            alias: self.alias.clone(),
+            tag_start: self.tag_start.clone(),
+            tag_end: self.tag_end.clone(),
+            method: self.method.clone(),
+            exit: self.exit,
+            next_content: self.next_content.clone(),
+            priority: self.priority,
+            r#async: self.r#async,
+            home: self.home,
+            launcher_type,
+            shortcut: self.shortcut,
+            spawn_focus: self.spawn_focus,
+            actions: self.actions.clone(),
+            add_actions: self.add_actions.clone(),
+            binds: self.binds.clone(),
+        }
+    }
+    /// Creates the launcher for a single result of a script returning multiple results.
+    /// Children inherit alias, home and priority so they are filtered like their parent.
+    pub fn async_child(&self, response: AsyncCommandResponse) -> Self {
+        let icon = match &self.launcher_type {
+            LauncherType::Api(api) => response.icon.or(Some(api.icon.clone())),
+            LauncherType::Plugin(plugin) => response.icon.or(plugin.icon()),
+            _ => response.icon.or(self.icon.clone()),
+        };
+        let method = if let Some(method) = response.method {
+            method
+        } else if response.next_content.is_some() {
+            String::from("next")
+        } else {
+            self.method.clone()
+        };
+        let launcher_type = LauncherType::Pipe(PipeLauncher {
+            id: None,
+            binary: None,
+            description: response.content,
+            hidden: response.hidden,
+            field: None,
+            icon_size: None,
+            result: response.result,
+        });
+        Self {
+            name: response.title,
+            icon,
+            alias: self.alias.clone(),
+            tag_start: None,
+            tag_end: None,
+            method,
+            exit: self.exit,
+            next_content: response.next_content,
+            priority: self.priority,
+            r#async: false,
+            home: self.home,
+            launcher_type,
+            shortcut: self.shortcut,
+            spawn_focus: self.spawn_focus,
+            actions: response.actions,
+            add_actions: None,
+            binds: None,
+        }
+    }
 }
 
 impl Launcher {
@@ -192,11 +288,13 @@ impl Launcher {
         match self.launcher_type {
             LauncherType::App(_)
             | LauncherType::Bookmark(_)
+            | LauncherType::CalcHistory(_)
             | LauncherType::Category(_)
             | LauncherType::Command(_)
             | LauncherType::Emoji(_)
             | LauncherType::File(_)
             | LauncherType::Process(_)
+            | LauncherType::RecentFiles(_)
             | LauncherType::Theme(_) => {
                 // Get app data value
                 let Some(inner) = self.inner() else {
@@ -213,10 +311,30 @@ impl Launcher {
                     })
                     .collect()
             }
+            // Every color conversion gets its own tile
+            LauncherType::Clipboard(ref clp) if !clp.colors.is_empty() => (0..clp.colors.len())
+                .map(|i| {
+                    let base = self.base_setup(launcher.clone());
+                    base.set_index(i);
+                    base
+                })
+                .collect(),
+            // As does every text operation
+            LauncherType::Transform(ref trf) => (0..trf.operations.len())
+                .map(|i| {
+                    let base = self.base_setup(launcher.clone());
+                    base.set_index(i);
+                    base
+                })
+                .collect(),
             LauncherType::Api(_)
             | LauncherType::Calc(_)
             | LauncherType::Clipboard(_)
             | LauncherType::Event(_)
+            | LauncherType::Generate(_)
+            | LauncherType::MostUsed(_)
+            | LauncherType::Plugin(_)
+            | LauncherType::Script(_)
             | LauncherType::Web(_)
             | LauncherType::Weather(_)
             | LauncherType::MusicPlayer(_)
@@ -238,15 +356,19 @@ impl Launcher {
         let handler = match &self.launcher_type {
             LauncherType::App(_)
             | LauncherType::Bookmark(_)
+            | LauncherType::CalcHistory(_)
             | LauncherType::Category(_)
             | LauncherType::Command(_)
             | LauncherType::Emoji(_)
             | LauncherType::File(_)
             | LauncherType::Process(_)
+            | LauncherType::RecentFiles(_)
             | LauncherType::Theme(_) => {
                 UpdateHandler::AppTile(AppTileHandler::new(launcher.clone()))
             }
-            LauncherType::Api(_) => UpdateHandler::ApiTile(ApiTileHandler::new(launcher.clone())),
+            LauncherType::Api(_) | LauncherType::Plugin(_) | LauncherType::Script(_) => {
+                UpdateHandler::ApiTile(ApiTileHandler::new(launcher.clone()))
+            }
             LauncherType::Calc(_) => {
                 UpdateHandler::Calculator(CalcTileHandler::new(launcher.clone()))
             }
@@ -254,6 +376,13 @@ impl Launcher {
             LauncherType::Event(evt) => {
                 UpdateHandler::Event(EventTileHandler::new(launcher.clone(), evt))
             }
+            LauncherType::Generate(gen) => UpdateHandler::Generate(GenerateTileHandler::new(
+                launcher.clone(),
+                gen.password_length,
+            )),
+            LauncherType::MostUsed(_) => {
+                UpdateHandler::MostUsed(MostUsedTileHandler::new(launcher.clone()))
+            }
             LauncherType::MusicPlayer(mpris) => {
                 UpdateHandler::MusicPlayer(MusicTileHandler::new(mpris, launcher.clone()))
             }
@@ -262,6 +391,9 @@ impl Launcher {
             LauncherType::Weather(_) => {
                 UpdateHandler::Weather(WeatherTileHandler::new(launcher.clone()))
             }
+            LauncherType::Transform(_) => {
+                UpdateHandler::Transform(TransformTileHandler::new(launcher.clone()))
+            }
             LauncherType::Web(_) => UpdateHandler::WebTile(WebTileHandler::default()),
             LauncherType::Empty => UpdateHandler::Default,
         };
@@ -277,12 +409,14 @@ impl Launcher {
         match &self.launcher_type {
             LauncherType::App(app) => Some(&app.apps),
             LauncherType::Bookmark(bkm) => Some(&bkm.bookmarks),
+            LauncherType::CalcHistory(hst) => Some(&hst.entries),
             LauncherType::Category(cat) => Some(&cat.categories),
             LauncherType::Command(cmd) => Some(&cmd.commands),
             LauncherType::Emoji(emj) => Some(&emj.data),
             LauncherType::File(f) => Some(&f.data),
             LauncherType::Theme(thm) => Some(&thm.themes),
             LauncherType::Process(proc) => Some(&proc.processes),
+            LauncherType::RecentFiles(rf) => Some(&rf.files),
             LauncherType::Web(web) => Some(&web.app_data),
             _ => None,
         }
@@ -322,9 +456,11 @@ impl Launcher {
             _ => None,
         }
     }
-    pub async fn get_result(&self, keyword: &str) -> Option<AsyncCommandResponse> {
+    pub async fn get_result(&self, keyword: &str) -> Option<AsyncCommandOutput> {
         match &self.launcher_type {
             LauncherType::Api(bulk_text) => bulk_text.get_result(keyword).await,
+            LauncherType::Plugin(plugin) => plugin.get_result(keyword).await,
+            LauncherType::Script(script) => script.get_result(keyword),
             _ => None,
         }
     }
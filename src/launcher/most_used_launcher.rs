@@ -0,0 +1,88 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::loader::util::{AppData, CounterReader};
+
+/// A dashboard grid of the apps launched most often
+#[derive(Clone, Debug)]
+pub struct MostUsedLauncher {
+    /// Installed apps the grid is picked from
+    pub apps: Vec<AppData>,
+    pub limit: usize,
+    /// Only launches within the last `window` days are counted. All launches count if `None`.
+    pub window: Option<u64>,
+    pub icon_size: i32,
+}
+impl MostUsedLauncher {
+    /// The most used apps, read from the execution counters every time the grid is built
+    pub fn top_apps(&self) -> Vec<AppData> {
+        let Ok(reader) = CounterReader::new() else {
+            return vec![];
+        };
+        let counts = match self.window {
+            Some(days) => {
+                let since = SystemTime::now()
+                    .checked_sub(Duration::from_secs(days.saturating_mul(86_400)))
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_secs());
+                reader.counts_since(since)
+            }
+            None => reader.counts().0,
+        };
+        rank_apps(&self.apps, &counts, self.limit)
+    }
+}
+
+/// Apps ordered by their execution count. Apps that were never launched are left out.
+fn rank_apps(apps: &[AppData], counts: &HashMap<String, u32>, limit: usize) -> Vec<AppData> {
+    let mut seen = HashSet::new();
+    let mut ranked: Vec<(u32, &AppData)> = apps
+        .iter()
+        .filter_map(|app| {
+            let exec = app.exec.as_ref()?;
+            let count = *counts.get(exec)?;
+            // Desktop files can share an exec
+            (count > 0 && seen.insert(exec)).then_some((count, app))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(_, app)| app.clone())
+        .collect()
+}
+
+#[test]
+fn test_rank_apps() {
+    let app = |name: &str, exec: &str| {
+        let mut app = AppData::new();
+        app.name = name.to_string();
+        app.exec = Some(exec.to_string());
+        app
+    };
+    let apps = vec![
+        app("Firefox", "firefox %u"),
+        app("Files", "nautilus"),
+        app("GIMP", "gimp %U"),
+        app("Firefox Copy", "firefox %u"),
+        app("Terminal", "kitty"),
+    ];
+    let counts = HashMap::from([
+        (String::from("firefox %u"), 4),
+        (String::from("gimp %U"), 7),
+        (String::from("kitty"), 4),
+        (String::from("nautilus"), 0),
+        (String::from("websearch-google"), 9),
+    ]);
+
+    let names = |limit: usize| -> Vec<String> {
+        rank_apps(&apps, &counts, limit)
+            .into_iter()
+            .map(|app| app.name)
+            .collect()
+    };
+    // Ties are ordered by name, duplicates and unused apps are skipped
+    assert_eq!(names(10), vec!["GIMP", "Firefox", "Terminal"]);
+    assert_eq!(names(2), vec!["GIMP", "Firefox"]);
+}
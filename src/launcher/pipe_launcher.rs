@@ -2,6 +2,8 @@ use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
 pub struct PipeLauncher {
+    /// Identifies the element across runs, e.g. to pin it
+    pub id: Option<String>,
     pub binary: Option<Vec<u8>>,
     pub description: Option<String>,
     pub hidden: Option<HashMap<String, String>>,
@@ -0,0 +1,391 @@
+use gio::glib::MainContext;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::time::Duration;
+
+use crate::api::{call::ApiCall, server::SherlockServer};
+use crate::loader::util::ApplicationAction;
+use crate::sherlock_error;
+use crate::utils::errors::{SherlockError, SherlockErrorType};
+use crate::utils::files::{expand_path, home_dir};
+
+use super::bulk_text_launcher::{AsyncCommandOutput, AsyncCommandResponse};
+use super::stdio_worker::{StdioWorker, WorkerError};
+
+/// Running plugins, keyed by their command line. Kept global so the process survives
+/// launcher reloads and activations can find the plugin that produced an item.
+static PLUGINS: Lazy<Mutex<HashMap<String, Arc<PluginClient>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Launcher backed by an external executable speaking newline-delimited JSON-RPC 2.0 on
+/// its stdin/stdout. See `docs/features/plugins.md` for the protocol.
+#[derive(Clone, Debug)]
+pub struct PluginLauncher {
+    pub exec: String,
+    pub args: Vec<String>,
+    pub icon: Option<String>,
+    pub timeout: Duration,
+    /// Passed along to the plugin on `initialize`
+    pub settings: Value,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PluginInfo {
+    pub name: Option<String>,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginQueryResult {
+    #[serde(default)]
+    items: Vec<PluginItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginItem {
+    id: String,
+    title: Option<String>,
+    description: Option<String>,
+    icon: Option<String>,
+    result: Option<String>,
+    #[serde(default)]
+    actions: Vec<PluginAction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginAction {
+    id: String,
+    name: Option<String>,
+    icon: Option<String>,
+}
+
+enum CallError {
+    Worker(WorkerError),
+    Rpc(String),
+}
+
+#[derive(Default)]
+pub struct PluginClient {
+    /// Only locked while the plugin gets started, so it is spawned once
+    worker: tokio::sync::Mutex<Option<Arc<StdioWorker>>>,
+    info: Mutex<Option<PluginInfo>>,
+    /// Latest launcher config, used to restart the plugin on activations
+    launcher: Mutex<Option<PluginLauncher>>,
+    next_id: AtomicU64,
+    latest_query: AtomicU64,
+    /// The query still waiting for its response
+    pending_query: Mutex<Option<u64>>,
+}
+
+impl PluginLauncher {
+    fn key(&self) -> String {
+        format!("{} {}", self.exec, self.args.join(" "))
+    }
+    pub fn client(&self) -> Arc<PluginClient> {
+        let mut plugins = PLUGINS.lock().unwrap();
+        let client = plugins.entry(self.key()).or_default().clone();
+        if let Ok(mut launcher) = client.launcher.lock() {
+            *launcher = Some(self.clone());
+        }
+        client
+    }
+    /// Icon to use for the launcher tile. Prefers the configured icon over the one the
+    /// plugin announced during `initialize`.
+    pub fn icon(&self) -> Option<String> {
+        self.icon
+            .clone()
+            .or_else(|| self.client().info().and_then(|i| i.icon))
+    }
+    pub async fn get_result(&self, keyword: &str) -> Option<AsyncCommandOutput> {
+        let client = self.client();
+        let items = match client.query(self, keyword).await {
+            Ok(Some(value)) => value,
+            Ok(None) => return None,
+            Err(e) => {
+                let response = AsyncCommandResponse {
+                    title: Some(String::from("Plugin failed to respond.")),
+                    content: Some(e.traceback),
+                    ..Default::default()
+                };
+                return Some(AsyncCommandOutput::Single(response));
+            }
+        };
+
+        let default_icon = self.icon();
+        let key = self.key();
+        let responses = items
+            .into_iter()
+            .map(|item| {
+                let payload = json!({"plugin": key, "id": item.id});
+                let actions: Vec<ApplicationAction> = item
+                    .actions
+                    .into_iter()
+                    .map(|action| {
+                        let payload = json!({"plugin": key, "id": item.id, "action": action.id});
+                        ApplicationAction {
+                            name: action.name,
+                            exec: Some(payload.to_string()),
+                            icon: action.icon,
+                            method: String::from("plugin"),
+                            exit: true,
+                            target: None,
+                            terminal: false,
+                        }
+                    })
+                    .collect();
+
+                AsyncCommandResponse {
+                    title: item.title,
+                    content: item.description,
+                    result: item.result,
+                    icon: item.icon.or(default_icon.clone()),
+                    method: Some(String::from("plugin")),
+                    hidden: Some(HashMap::from([(String::from("exec"), payload.to_string())])),
+                    actions: (!actions.is_empty()).then_some(actions),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        Some(AsyncCommandOutput::Multiple(responses))
+    }
+    /// Asks an already running plugin to reload its data. Plugins that were not started yet
+    /// will load fresh data on `initialize` anyway.
+    pub fn refresh(&self) {
+        let client = self.client();
+        if !client.supports("refresh") {
+            return;
+        }
+        let launcher = self.clone();
+        MainContext::default().spawn_local(async move {
+            if let Err(e) = client.request(&launcher, "refresh", json!({})).await {
+                let _result = e.insert(false);
+            }
+        });
+    }
+}
+
+impl PluginClient {
+    pub fn info(&self) -> Option<PluginInfo> {
+        self.info.lock().ok().and_then(|i| i.clone())
+    }
+    fn supports(&self, capability: &str) -> bool {
+        self.info()
+            .map_or(false, |i| i.capabilities.iter().any(|c| c == capability))
+    }
+    /// Sends a `query` request. Returns `None` if a newer query was issued in the meantime.
+    async fn query(
+        &self,
+        launcher: &PluginLauncher,
+        keyword: &str,
+    ) -> Result<Option<Vec<PluginItem>>, SherlockError> {
+        let ticket = self.latest_query.fetch_add(1, Ordering::SeqCst) + 1;
+        let worker = self.worker(launcher).await?;
+        // A newer query arrived while the plugin was starting
+        if self.latest_query.load(Ordering::SeqCst) != ticket {
+            return Ok(None);
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        // Stop waiting for the previous query, its late response gets dropped
+        let stale = self
+            .pending_query
+            .lock()
+            .ok()
+            .and_then(|mut p| p.replace(id));
+        if let Some(stale) = stale {
+            worker.cancel(stale);
+        }
+
+        let params = json!({"query": keyword});
+        let result = Self::call(&worker, id, "query", params, launcher.timeout).await;
+        if let Ok(mut pending) = self.pending_query.lock() {
+            if *pending == Some(id) {
+                *pending = None;
+            }
+        }
+        let value = match result {
+            Err(CallError::Worker(WorkerError::Cancelled)) => return Ok(None),
+            result => Self::check(launcher, "query", result)?,
+        };
+
+        let result: PluginQueryResult = serde_json::from_value(value).map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::PluginError(launcher.exec.clone()),
+                e.to_string()
+            )
+        })?;
+        Ok(Some(result.items))
+    }
+    pub async fn request(
+        &self,
+        launcher: &PluginLauncher,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, SherlockError> {
+        let worker = self.worker(launcher).await?;
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let result = Self::call(&worker, id, method, params, launcher.timeout).await;
+        Self::check(launcher, method, result)
+    }
+    /// The running plugin. It is started again if it exited or was not started yet.
+    async fn worker(&self, launcher: &PluginLauncher) -> Result<Arc<StdioWorker>, SherlockError> {
+        let mut guard = self.worker.lock().await;
+        if let Some(worker) = guard.as_ref().filter(|w| !w.is_closed()) {
+            return Ok(worker.clone());
+        }
+        let worker = self.start(launcher).await.map_err(|e| {
+            sherlock_error!(SherlockErrorType::PluginError(launcher.exec.clone()), e)
+        })?;
+        let worker = Arc::new(worker);
+        *guard = Some(worker.clone());
+        Ok(worker)
+    }
+    /// Spawns the plugin and performs the `initialize` handshake
+    async fn start(&self, launcher: &PluginLauncher) -> Result<StdioWorker, String> {
+        let home = home_dir().map_err(|e| e.to_string())?;
+        let exec = expand_path(&launcher.exec, &home);
+        let worker = StdioWorker::spawn(&exec, &launcher.args).map_err(|e| e.to_string())?;
+
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst) + 1;
+        let params = json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "settings": launcher.settings,
+        });
+        let value = match Self::call(&worker, id, "initialize", params, launcher.timeout).await {
+            Ok(value) => value,
+            Err(CallError::Worker(WorkerError::Io(e))) | Err(CallError::Rpc(e)) => return Err(e),
+            Err(CallError::Worker(WorkerError::Timeout)) => {
+                return Err(String::from("Initialization timed out"))
+            }
+            Err(CallError::Worker(WorkerError::Cancelled)) => {
+                return Err(String::from("Initialization was cancelled"))
+            }
+        };
+        let info: PluginInfo = serde_json::from_value(value).unwrap_or_default();
+        if let Ok(mut guard) = self.info.lock() {
+            *guard = Some(info);
+        }
+        Ok(worker)
+    }
+    async fn call(
+        worker: &StdioWorker,
+        id: u64,
+        method: &str,
+        params: Value,
+        limit: Duration,
+    ) -> Result<Value, CallError> {
+        let message = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        let response = worker
+            .request(id, &message, limit)
+            .await
+            .map_err(CallError::Worker)?;
+
+        if let Some(error) = response.get("error") {
+            let message = error
+                .get("message")
+                .and_then(Value::as_str)
+                .map_or_else(|| error.to_string(), str::to_string);
+            return Err(CallError::Rpc(message));
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+    /// Turns a failed call into a plugin error. Plugins that exited are restarted on the
+    /// next request.
+    fn check(
+        launcher: &PluginLauncher,
+        method: &str,
+        result: Result<Value, CallError>,
+    ) -> Result<Value, SherlockError> {
+        let message = match result {
+            Ok(value) => return Ok(value),
+            Err(CallError::Worker(WorkerError::Io(e))) | Err(CallError::Rpc(e)) => e,
+            Err(CallError::Worker(WorkerError::Timeout)) => format!(
+                "Request \"{}\" timed out after {:?}",
+                method, launcher.timeout
+            ),
+            Err(CallError::Worker(WorkerError::Cancelled)) => {
+                format!("Request \"{}\" was cancelled", method)
+            }
+        };
+        Err(sherlock_error!(
+            SherlockErrorType::PluginError(launcher.exec.clone()),
+            message
+        ))
+    }
+}
+
+/// Forwards the activation of a plugin item or one of its actions to the plugin. Closing
+/// is deferred until the plugin answered, which can override it using `{"close": bool}`.
+pub fn activate(payload: &str, close: bool) {
+    let Ok(payload) = serde_json::from_str::<Value>(payload) else {
+        return;
+    };
+    let Some(key) = payload.get("plugin").and_then(Value::as_str) else {
+        return;
+    };
+    let Some(client) = PLUGINS.lock().ok().and_then(|p| p.get(key).cloned()) else {
+        return;
+    };
+    let params = json!({
+        "id": payload.get("id").cloned().unwrap_or(Value::Null),
+        "action": payload.get("action").cloned().unwrap_or(Value::Null),
+    });
+
+    MainContext::default().spawn_local(async move {
+        let Some(launcher) = client.launcher.lock().ok().and_then(|l| l.clone()) else {
+            return;
+        };
+        match client.request(&launcher, "activate", params).await {
+            Ok(value) => {
+                let close = value.get("close").and_then(Value::as_bool).unwrap_or(close);
+                if close {
+                    let _ = SherlockServer::send_action(ApiCall::Close);
+                }
+            }
+            Err(e) => {
+                let _result = e.insert(false);
+            }
+        }
+    });
+}
+
+#[tokio::test]
+async fn test_plugin_round_trip() {
+    // Answers `initialize` and `query` with the id of the request
+    let script = r#"
+        while IFS= read -r line; do
+            id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+            case "$line" in
+                *'"initialize"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"icon\":\"stub\"}}" ;;
+                *'"query"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"items\":[{\"id\":\"a\",\"title\":\"A\"},{\"id\":\"b\",\"title\":\"B\",\"actions\":[{\"id\":\"x\"}]}]}}" ;;
+            esac
+        done
+    "#;
+    let launcher = PluginLauncher {
+        exec: String::from("sh"),
+        args: vec![String::from("-c"), script.to_string()],
+        icon: None,
+        timeout: Duration::from_secs(2),
+        settings: Value::Null,
+    };
+
+    let Some(AsyncCommandOutput::Multiple(items)) = launcher.get_result("query").await else {
+        panic!("expected multiple results");
+    };
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].title.as_deref(), Some("A"));
+    assert_eq!(items[0].icon.as_deref(), Some("stub"));
+    assert_eq!(items[1].actions.as_ref().map(Vec::len), Some(1));
+}
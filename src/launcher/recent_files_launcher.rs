@@ -0,0 +1,384 @@
+use chrono::{DateTime, Local, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+use xml::reader::{EventReader, XmlEvent};
+
+use crate::actions::applaunch::{accepts_targets, to_path};
+use crate::loader::application_loader::{get_data_dirs, parse_priority};
+use crate::loader::mime_apps::MIME_APPS;
+use crate::loader::util::{AppData, ApplicationAction, RawLauncher};
+use crate::sherlock_error;
+use crate::utils::errors::{SherlockError, SherlockErrorType};
+
+#[derive(Clone, Debug)]
+pub struct RecentFilesLauncher {
+    pub files: Vec<AppData>,
+}
+
+/// A `<bookmark>` of `recently-used.xbel`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RecentFile {
+    pub href: String,
+    pub title: Option<String>,
+    pub mime_type: Option<String>,
+    pub modified: Option<DateTime<Utc>>,
+    /// Applications that registered the file
+    pub apps: Vec<RecentApp>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentApp {
+    pub name: String,
+    pub exec: String,
+    pub modified: Option<DateTime<Utc>>,
+}
+
+impl RecentFilesLauncher {
+    pub fn new(raw: &RawLauncher, limit: usize) -> Result<Self, SherlockError> {
+        let path = Self::history()?;
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => Err(sherlock_error!(
+                SherlockErrorType::FileReadError(path),
+                e.to_string()
+            ))?,
+        };
+
+        let mut files: Vec<RecentFile> = parse_history(&content)
+            .into_iter()
+            // Deleted files stay in the history until an app cleans it up
+            .filter(|file| {
+                !file.href.starts_with("file://") || Path::new(&to_path(&file.href)).exists()
+            })
+            .collect();
+        files.sort_by(|a, b| b.last_used().cmp(&a.last_used()));
+        files.truncate(limit);
+
+        let folder_exec = MIME_APPS
+            .read()
+            .ok()
+            .and_then(|apps| apps.default_app("inode/directory"))
+            .map(|app| app.exec)
+            .filter(|exec| accepts_targets(exec))
+            .unwrap_or_else(|| String::from("xdg-open %f"));
+
+        // Newer files get a higher rank
+        let decimals = files.len().to_string().len() as i32;
+        let total = files.len() as u32;
+        let files = files
+            .into_iter()
+            .enumerate()
+            .map(|(i, file)| {
+                let mut data = file.to_app_data(raw, &folder_exec);
+                data.priority = parse_priority(raw.priority, total - i as u32, decimals);
+                data
+            })
+            .collect();
+        Ok(Self { files })
+    }
+
+    /// `$XDG_DATA_HOME/recently-used.xbel`
+    fn history() -> Result<PathBuf, SherlockError> {
+        get_data_dirs()
+            .into_iter()
+            .next()
+            .map(|dir| dir.join("recently-used.xbel"))
+            .ok_or_else(|| {
+                sherlock_error!(
+                    SherlockErrorType::DirReadError(String::from("XDG_DATA_HOME")),
+                    "Could not find data directory"
+                )
+            })
+    }
+
+    /// Removes a file from the history. The rest of the file is kept as it is.
+    pub fn remove(href: &str) -> Result<(), SherlockError> {
+        let path = Self::history()?;
+        let content = fs::read_to_string(&path).map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::FileReadError(path.clone()),
+                e.to_string()
+            )
+        })?;
+        let Some(content) = remove_bookmark(&content, href) else {
+            return Ok(());
+        };
+
+        // Replace the file at once so apps never read a partial history
+        let tmp = path.with_extension("xbel.sherlock");
+        fs::write(&tmp, content)
+            .and_then(|_| fs::rename(&tmp, &path))
+            .map_err(|e| sherlock_error!(SherlockErrorType::FileWriteError(path), e.to_string()))
+    }
+}
+
+impl RecentFile {
+    /// The app that used the file last
+    fn last_app(&self) -> Option<&RecentApp> {
+        self.apps.iter().max_by_key(|app| app.modified)
+    }
+    fn last_used(&self) -> Option<DateTime<Utc>> {
+        self.last_app()
+            .and_then(|app| app.modified)
+            .or(self.modified)
+    }
+
+    /// `folder_exec` opens the containing folder
+    fn to_app_data(&self, raw: &RawLauncher, folder_exec: &str) -> AppData {
+        let is_local = self.href.starts_with("file://");
+        let path = to_path(&self.href);
+        let name = self.title.clone().unwrap_or_else(|| {
+            Path::new(&path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or(&path)
+                .to_string()
+        });
+
+        // Open with the app that recorded the file, or with the default app for its type
+        let app = self.last_app();
+        let default_app = || {
+            self.mime_type
+                .as_deref()
+                .and_then(|mime| MIME_APPS.read().ok()?.default_app(mime))
+        };
+        let mut exec = app
+            .map(|app| unquote(&app.exec))
+            .or_else(|| default_app().map(|app| app.exec))
+            .unwrap_or_else(|| String::from("xdg-open %u"));
+        if !accepts_targets(&exec) {
+            exec.push_str(" %u");
+        }
+
+        let used = self.last_used().map(|time| {
+            time.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        });
+        let app_name = app.map(|app| app.name.clone());
+        let comment = match (app_name, used) {
+            (Some(app), Some(used)) => Some(format!("{} · {}", app, used)),
+            (app, used) => app.or(used),
+        };
+
+        let mut data = AppData::new();
+        data.search_string = format!("{};{}", name, path);
+        data.name = name;
+        data.comment = comment;
+        data.icon = self
+            .mime_type
+            .as_ref()
+            .map(|mime| mime.replace('/', "-"))
+            .or_else(|| Some(String::from("text-x-generic")));
+        data.icon_class = raw
+            .args
+            .get("icon_class")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        data.tag_start = raw.tag_start.clone();
+        data.tag_end = raw.tag_end.clone();
+        data.exec = Some(exec);
+        data.target = Some(self.href.clone());
+
+        if is_local {
+            if let Some(parent) = Path::new(&path).parent().and_then(|p| p.to_str()) {
+                let mut action = ApplicationAction::new("app_launcher");
+                action.name = Some(String::from("Open Containing Folder"));
+                action.exec = Some(folder_exec.to_string());
+                action.icon = Some(String::from("folder-open"));
+                action.target = Some(parent.to_string());
+                data.actions.push(action);
+            }
+        }
+        let mut action = ApplicationAction::new("copy");
+        action.name = Some(String::from("Copy Path"));
+        action.exec = Some(if is_local { path } else { self.href.clone() });
+        action.icon = Some(String::from("edit-copy"));
+        data.actions.push(action);
+
+        let mut action = ApplicationAction::new("remove_recent");
+        action.name = Some(String::from("Remove from History"));
+        action.exec = Some(self.href.clone());
+        action.icon = Some(String::from("edit-delete"));
+        data.actions.push(action);
+
+        data
+    }
+}
+
+/// Parses the bookmarks of a `recently-used.xbel` file as written by GLib's `GBookmarkFile`.
+/// Parsing stops at the first syntax error.
+pub fn parse_history(content: &str) -> Vec<RecentFile> {
+    let timestamp = |value: Option<String>| {
+        value
+            .and_then(|v| DateTime::parse_from_rfc3339(&v).ok())
+            .map(|time| time.with_timezone(&Utc))
+    };
+
+    let mut files = Vec::new();
+    let mut current: Option<RecentFile> = None;
+    let mut in_title = false;
+    for event in EventReader::from_str(content) {
+        match event {
+            Ok(XmlEvent::StartElement {
+                name, attributes, ..
+            }) => {
+                let attr = |key: &str| {
+                    attributes
+                        .iter()
+                        .find(|a| a.name.local_name == key)
+                        .map(|a| a.value.clone())
+                };
+                if name.prefix.is_none() && name.local_name == "bookmark" {
+                    current = attr("href").map(|href| RecentFile {
+                        href,
+                        modified: timestamp(attr("modified")),
+                        ..Default::default()
+                    });
+                    continue;
+                }
+                let Some(file) = current.as_mut() else {
+                    continue;
+                };
+                match name.local_name.as_str() {
+                    "title" => in_title = true,
+                    "mime-type" => file.mime_type = attr("type"),
+                    "application" => {
+                        if let (Some(name), Some(exec)) = (attr("name"), attr("exec")) {
+                            file.apps.push(RecentApp {
+                                name,
+                                exec,
+                                modified: timestamp(attr("modified")),
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Ok(XmlEvent::Characters(text)) if in_title => {
+                if let Some(file) = current.as_mut() {
+                    file.title = Some(text);
+                }
+            }
+            Ok(XmlEvent::EndElement { name }) => match name.local_name.as_str() {
+                "title" => in_title = false,
+                "bookmark" if name.prefix.is_none() => files.extend(current.take()),
+                _ => {}
+            },
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    files
+}
+
+/// Cuts the `<bookmark>` element of `href` including its line. Returns `None` if the file
+/// does not contain it.
+fn remove_bookmark(content: &str, href: &str) -> Option<String> {
+    let mut offset = 0;
+    while let Some(found) = content[offset..].find("<bookmark ") {
+        let start = offset + found;
+        let tag_end = start + content[start..].find('>')?;
+        offset = tag_end;
+        let tag = &content[start..tag_end];
+        let value = ["href=\"", "href='"].iter().find_map(|prefix| {
+            let rest = &tag[tag.find(prefix)? + prefix.len()..];
+            let quote = prefix.chars().last()?;
+            rest.split(quote).next()
+        });
+        if value.map(unescape_xml).as_deref() != Some(href) {
+            continue;
+        }
+
+        let end = if content[..=tag_end].ends_with("/>") {
+            tag_end + 1
+        } else {
+            let close = "</bookmark>";
+            tag_end + content[tag_end..].find(close)? + close.len()
+        };
+        // Drop the indentation and the line break as well
+        let start = content[..start].trim_end_matches([' ', '\t']).len();
+        let end = end + usize::from(content[end..].starts_with('\n'));
+        return Some(format!("{}{}", &content[..start], &content[end..]));
+    }
+    None
+}
+
+fn unescape_xml(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(amp) = rest.find('&') {
+        result.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';') else {
+            break;
+        };
+        let decoded = match &rest[1..semi] {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            entity => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                result.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// GLib quotes the whole command line of an app, e.g. `'evince %u'`
+fn unquote(exec: &str) -> String {
+    exec.strip_prefix('\'')
+        .and_then(|exec| exec.strip_suffix('\''))
+        .map(|exec| exec.replace("'\\''", "'"))
+        .unwrap_or_else(|| exec.to_string())
+}
+
+#[test]
+fn test_recent_files() {
+    let content = fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/recently-used.xbel"),
+    )
+    .unwrap();
+    let files = parse_history(&content);
+    assert_eq!(files.len(), 3);
+
+    let report = &files[0];
+    assert_eq!(
+        report.href,
+        "file:///home/user/Documents/Q%26A%20report.pdf"
+    );
+    assert_eq!(report.mime_type.as_deref(), Some("application/pdf"));
+    assert_eq!(report.apps.len(), 2);
+    let last = report.last_app().unwrap();
+    assert_eq!(last.name, "Document Viewer");
+    assert_eq!(unquote(&last.exec), "evince %u");
+    assert_eq!(
+        report.last_used().map(|t| t.to_rfc3339()).as_deref(),
+        Some("2025-03-02T09:30:00.123456+00:00")
+    );
+    assert_eq!(files[1].title.as_deref(), Some("Notes"));
+    assert_eq!(unquote("'it'\\''s %f'"), "it's %f");
+
+    // Removing keeps the other bookmarks untouched
+    let removed = remove_bookmark(&content, &report.href).unwrap();
+    let rest = parse_history(&removed);
+    assert_eq!(rest, files[1..].to_vec());
+    assert!(removed.contains("<bookmark href=\"https://example.com/?a=1&amp;b=2\""));
+    assert!(remove_bookmark(&content, "file:///missing").is_none());
+}
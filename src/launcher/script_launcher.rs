@@ -0,0 +1,175 @@
+use once_cell::sync::Lazy;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, Map, Scope, AST};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::loader::util::ApplicationAction;
+use crate::sher_log;
+
+use super::bulk_text_launcher::{AsyncCommandOutput, AsyncCommandResponse};
+
+/// Shared scripting engine. Scripts cannot touch the file system or spawn processes. The
+/// action functions only describe what should happen once the user executes an item.
+static ENGINE: Lazy<Engine> = Lazy::new(|| {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.set_max_operations(500_000);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(1 << 16);
+    engine.set_max_array_size(1024);
+    engine.set_max_map_size(1024);
+    engine.on_print(|msg| {
+        let _ = sher_log!(format!("[script] {}", msg));
+    });
+    engine.on_debug(|msg, source, pos| {
+        let _ = sher_log!(format!(
+            "[script] {} {:?} {}",
+            source.unwrap_or(""),
+            pos,
+            msg
+        ));
+    });
+
+    engine.register_fn("copy", |text: &str| action("copy", text));
+    engine.register_fn("open", |target: &str| action("web_launcher", target));
+    engine.register_fn("command_launch", |exec: &str| action("command", exec));
+    engine.register_fn("action", |name: &str, mut action: Map| {
+        action.insert("name".into(), name.into());
+        action
+    });
+    engine
+});
+
+fn action(method: &str, exec: &str) -> Map {
+    let mut map = Map::new();
+    map.insert("method".into(), method.into());
+    map.insert("exec".into(), exec.into());
+    map
+}
+
+/// Launcher defined by a Rhai script. The script is compiled once while loading the
+/// launchers and its `query` function is called in-process on every keystroke.
+#[derive(Clone, Debug)]
+pub struct ScriptLauncher {
+    /// Script path or launcher name, used in error messages
+    pub name: String,
+    pub ast: Arc<AST>,
+}
+
+impl ScriptLauncher {
+    pub fn compile(name: String, source: &str) -> Result<Self, String> {
+        let ast = ENGINE.compile(source).map_err(|e| e.to_string())?;
+        Ok(Self {
+            name,
+            ast: Arc::new(ast),
+        })
+    }
+    pub fn get_result(&self, keyword: &str) -> Option<AsyncCommandOutput> {
+        let value = ENGINE.call_fn::<Dynamic>(
+            &mut Scope::new(),
+            &self.ast,
+            "query",
+            (keyword.to_string(),),
+        );
+        let value = match value {
+            Ok(value) => value,
+            Err(e) => {
+                let response = AsyncCommandResponse {
+                    title: Some(format!("Script \"{}\" failed.", self.name)),
+                    content: Some(e.to_string()),
+                    ..Default::default()
+                };
+                return Some(AsyncCommandOutput::Single(response));
+            }
+        };
+
+        // Scripts may return a single item, an array of items or nothing at all
+        let items: Vec<Map> = if let Some(map) = value.clone().try_cast::<Map>() {
+            vec![map]
+        } else if let Some(array) = value.try_cast::<rhai::Array>() {
+            array
+                .into_iter()
+                .filter_map(|item| item.try_cast::<Map>())
+                .collect()
+        } else {
+            return None;
+        };
+
+        Some(AsyncCommandOutput::Multiple(
+            items.iter().map(Self::to_response).collect(),
+        ))
+    }
+    fn to_response(item: &Map) -> AsyncCommandResponse {
+        let on_return = item
+            .get("on_return")
+            .and_then(|a| a.clone().try_cast::<Map>());
+        let method = on_return.as_ref().and_then(|a| string(a, "method"));
+        let exec = on_return.as_ref().and_then(|a| string(a, "exec"));
+        // Copying always uses the result, so `copy(text)` has to replace it
+        let result = match method.as_deref() {
+            Some("copy") => exec.clone(),
+            _ => None,
+        }
+        .or_else(|| string(item, "result"));
+        let hidden = exec.map(|exec| HashMap::from([(String::from("exec"), exec)]));
+        let actions: Vec<ApplicationAction> = item
+            .get("actions")
+            .and_then(|a| a.clone().try_cast::<rhai::Array>())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|a| a.try_cast::<Map>())
+            .map(|a| ApplicationAction {
+                name: string(&a, "name"),
+                exec: string(&a, "exec"),
+                icon: string(&a, "icon"),
+                method: string(&a, "method").unwrap_or_default(),
+                exit: a.get("exit").and_then(|e| e.as_bool().ok()).unwrap_or(true),
+                target: None,
+                terminal: false,
+            })
+            .collect();
+
+        AsyncCommandResponse {
+            title: string(item, "title"),
+            content: string(item, "description"),
+            next_content: string(item, "next_content"),
+            result,
+            icon: string(item, "icon"),
+            method,
+            hidden,
+            actions: (!actions.is_empty()).then_some(actions),
+        }
+    }
+}
+
+fn string(map: &Map, key: &str) -> Option<String> {
+    map.get(key)
+        .filter(|value| !value.is_unit())
+        .map(|value| value.to_string())
+}
+
+#[test]
+fn test_script_items() {
+    let script = r#"
+        fn query(text) {
+            if text == "" { return; }
+            [
+                #{ title: text, result: text, on_return: copy(text.to_upper()) },
+                #{ title: "Search", actions: [action("Open", open("https://example.com"))] },
+            ]
+        }
+    "#;
+    let launcher = ScriptLauncher::compile(String::from("test"), script).unwrap();
+    assert!(launcher.get_result("").is_none());
+
+    let Some(AsyncCommandOutput::Multiple(items)) = launcher.get_result("abc") else {
+        panic!("expected multiple results");
+    };
+    assert_eq!(items[0].result.as_deref(), Some("ABC"));
+    assert_eq!(items[0].method.as_deref(), Some("copy"));
+    let actions = items[1].actions.as_ref().unwrap();
+    assert_eq!(actions[0].name.as_deref(), Some("Open"));
+    assert_eq!(actions[0].method, "web_launcher");
+}
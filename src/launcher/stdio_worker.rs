@@ -0,0 +1,181 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::{timeout, Duration};
+
+type Pending = Arc<Mutex<HashMap<u64, oneshot::Sender<Value>>>>;
+
+#[derive(Debug)]
+pub enum WorkerError {
+    /// The process died or its pipes broke
+    Io(String),
+    Timeout,
+    /// The request was cancelled before it got answered
+    Cancelled,
+}
+
+/// A long running child process exchanging newline-delimited JSON on its stdin/stdout.
+/// Every message carries an `"id"`. A reader task routes the responses to the request
+/// waiting for that id, so stdin is only locked while a message is written and slow
+/// requests never block newer ones.
+pub struct StdioWorker {
+    _child: Child,
+    stdin: tokio::sync::Mutex<ChildStdin>,
+    pending: Pending,
+    closed: Arc<AtomicBool>,
+    reader: JoinHandle<()>,
+}
+
+impl StdioWorker {
+    pub fn spawn(exec: &Path, args: &[String]) -> std::io::Result<Self> {
+        let mut child = Command::new(exec)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| std::io::Error::other("Failed to open stdin"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| std::io::Error::other("Failed to open stdout"))?;
+
+        let pending: Pending = Arc::new(Mutex::new(HashMap::new()));
+        let closed = Arc::new(AtomicBool::new(false));
+        let reader = tokio::spawn({
+            let pending = pending.clone();
+            let closed = closed.clone();
+            async move {
+                let mut lines = BufReader::new(stdout).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    // Skip notifications, garbage and responses nobody waits for anymore
+                    let Ok(value) = serde_json::from_str::<Value>(&line) else {
+                        continue;
+                    };
+                    let Some(id) = value.get("id").and_then(Value::as_u64) else {
+                        continue;
+                    };
+                    let sender = pending.lock().ok().and_then(|mut p| p.remove(&id));
+                    if let Some(sender) = sender {
+                        let _ = sender.send(value);
+                    }
+                }
+                // Wake up all waiting requests
+                closed.store(true, Ordering::SeqCst);
+                if let Ok(mut pending) = pending.lock() {
+                    pending.clear();
+                }
+            }
+        });
+
+        Ok(Self {
+            _child: child,
+            stdin: tokio::sync::Mutex::new(stdin),
+            pending,
+            closed,
+            reader,
+        })
+    }
+    /// Whether the process closed its output. Closed workers have to be spawned again.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+    /// Writes a single message without waiting for an answer
+    pub async fn write(&self, message: &Value) -> Result<(), WorkerError> {
+        let mut line = message.to_string();
+        line.push('\n');
+
+        let mut stdin = self.stdin.lock().await;
+        stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| WorkerError::Io(e.to_string()))?;
+        stdin
+            .flush()
+            .await
+            .map_err(|e| WorkerError::Io(e.to_string()))
+    }
+    /// Writes the message and waits up to `limit` for the response carrying `id`
+    pub async fn request(
+        &self,
+        id: u64,
+        message: &Value,
+        limit: Duration,
+    ) -> Result<Value, WorkerError> {
+        let (sender, receiver) = oneshot::channel();
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(id, sender);
+        }
+        if self.is_closed() {
+            self.cancel(id);
+            return Err(WorkerError::Io(String::from("Worker closed its output.")));
+        }
+        if let Err(e) = self.write(message).await {
+            self.cancel(id);
+            return Err(e);
+        }
+
+        match timeout(limit, receiver).await {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err(_)) if self.is_closed() => {
+                Err(WorkerError::Io(String::from("Worker closed its output.")))
+            }
+            Ok(Err(_)) => Err(WorkerError::Cancelled),
+            Err(_) => {
+                self.cancel(id);
+                Err(WorkerError::Timeout)
+            }
+        }
+    }
+    /// Stops waiting for the response to `id`. The pending request returns
+    /// [`WorkerError::Cancelled`] and a late response is dropped.
+    pub fn cancel(&self, id: u64) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&id);
+        }
+    }
+}
+
+impl Drop for StdioWorker {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+#[tokio::test]
+async fn test_out_of_order_responses() {
+    // Echoes two requests back in reverse order
+    let script = r#"read -r a; read -r b; echo "$b"; echo "$a"; read -r c"#;
+    let args = vec![String::from("-c"), script.to_string()];
+    let worker = StdioWorker::spawn(Path::new("sh"), &args).unwrap();
+    let limit = Duration::from_secs(2);
+
+    let first = serde_json::json!({"id": 1, "query": "a"});
+    let second = serde_json::json!({"id": 2, "query": "b"});
+    let (a, b) = tokio::join!(
+        worker.request(1, &first, limit),
+        worker.request(2, &second, limit)
+    );
+    assert_eq!(a.unwrap(), first);
+    assert_eq!(b.unwrap(), second);
+
+    // Cancelled requests return without waiting for a response
+    let third = serde_json::json!({"id": 3, "query": "c"});
+    let (c, _) = tokio::join!(worker.request(3, &third, limit), async {
+        tokio::task::yield_now().await;
+        worker.cancel(3);
+    });
+    assert!(matches!(c, Err(WorkerError::Cancelled)));
+}
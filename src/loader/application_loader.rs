@@ -10,7 +10,9 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::SystemTime;
 
-use super::util::ApplicationAction;
+use super::desktop_entry::{current_desktops, executable_exists, shown_in, DesktopEntry, Locale};
+use super::mime_apps::MimeApps;
+use super::util::{ApplicationAction, CounterReader};
 use super::{util, Loader};
 use crate::prelude::PathHelpers;
 use crate::utils::cache::BinaryCache;
@@ -70,98 +72,45 @@ impl Loader {
             _ => get_desktop_files(system_apps),
         };
 
+        // Resolved once for all files
+        let locale = Locale::from_env();
+        let desktops = current_desktops();
+
         // Parellize opening of all .desktop files and parsing them into AppData
         let apps: Vec<AppData> = desktop_files
             .into_par_iter()
             .filter_map(|entry| {
-                let r_path = entry.to_str()?;
-                match read_lines(r_path) {
-                    Ok(content) => {
-                        let mut data = AppData::new();
-                        let mut current_section = None;
-                        let mut current_action = ApplicationAction::new("app_launcher");
-                        data.desktop_file = Some(entry);
-                        for line in content.flatten() {
-                            let line = line.trim();
-                            // Skip useless lines
-                            if line.is_empty() || line.starts_with('#') {
-                                continue;
-                            }
-                            if line.starts_with('[') && line.ends_with(']') {
-                                current_section = Some(line[1..line.len() - 1].to_string());
-                                if current_action.is_valid() {
-                                    data.actions.push(current_action.clone())
-                                }
-                                current_action = ApplicationAction::new("app_launcher");
-                                continue;
-                            }
-                            if current_section.is_none() {
-                                continue;
-                            }
-                            if let Some((key, value)) = line.split_once('=') {
-                                let key = key.trim().to_ascii_lowercase();
-                                let value = value.trim();
-                                if current_section.as_deref().unwrap() == "Desktop Entry" {
-                                    match key.as_ref() {
-                                        "name" => {
-                                            data.name = {
-                                                if should_ignore(&ignore_apps, value) {
-                                                    return None;
-                                                }
-                                                value.to_string()
-                                            }
-                                        }
-                                        "icon" => data.icon = Some(value.to_string()),
-                                        "exec" => data.exec = Some(value.to_string()),
-                                        "nodisplay" if value.eq_ignore_ascii_case("true") => {
-                                            return None
-                                        }
-                                        "hidden" if value.eq_ignore_ascii_case("true") => {
-                                            return None
-                                        }
-                                        "terminal" => {
-                                            data.terminal = value.eq_ignore_ascii_case("true");
-                                        }
-                                        "keywords" => data.search_string = value.to_string(),
-                                        _ => {}
-                                    }
-                                } else {
-                                    // Application Actions
-                                    match key.as_ref() {
-                                        "name" => current_action.name = Some(value.to_string()),
-                                        "exec" => current_action.exec = Some(value.to_string()),
-                                        "icon" => current_action.icon = Some(value.to_string()),
-                                        _ => {}
-                                    }
-                                    if current_action.is_full() {
-                                        data.actions.push(current_action.clone());
-                                        current_action = ApplicationAction::new("app_launcher");
-                                        current_section = None;
-                                    }
-                                }
-                            }
-                        }
-                        data.actions
-                            .iter_mut()
-                            .filter(|action| action.icon.is_none())
-                            .for_each(|action| action.icon = data.icon.clone());
-                        let alias = {
-                            let mut aliases = aliases.lock_blocking();
-                            aliases.remove(&data.name)
-                        };
-                        data.apply_alias(alias, use_keywords);
-                        // apply counts
-                        let count = data
-                            .exec
-                            .as_ref()
-                            .and_then(|exec| counts.get(exec))
-                            .unwrap_or(&0);
-                        let priority = parse_priority(priority, *count, decimals);
-                        data.priority = priority;
-                        Some(data)
-                    }
-                    Err(_) => None,
+                let desktop_entry = DesktopEntry::read(&entry).ok()?;
+                let mut data =
+                    parse_desktop_entry(&desktop_entry, &entry, locale.as_ref(), &desktops)?;
+                // Ignore patterns and aliases may use the localized or the unlocalized name
+                let raw_name = desktop_entry
+                    .group("Desktop Entry")
+                    .and_then(|group| group.get("Name"));
+                if should_ignore(&ignore_apps, &data.name)
+                    || raw_name
+                        .as_deref()
+                        .is_some_and(|name| should_ignore(&ignore_apps, name))
+                {
+                    return None;
                 }
+                data.desktop_file = Some(entry);
+                let alias = {
+                    let mut aliases = aliases.lock_blocking();
+                    aliases
+                        .remove(&data.name)
+                        .or_else(|| raw_name.and_then(|name| aliases.remove(&name)))
+                };
+                data.apply_alias(alias, use_keywords);
+                // apply counts
+                let count = data
+                    .exec
+                    .as_ref()
+                    .and_then(|exec| counts.get(exec))
+                    .unwrap_or(&0);
+                let priority = parse_priority(priority, *count, decimals);
+                data.priority = priority;
+                Some(data)
             })
             .collect();
         Ok(apps)
@@ -219,6 +168,67 @@ impl Loader {
         return Ok(apps);
     }
 
+    /// Re-reads the desktop files affected by `changed`, which may contain files and
+    /// directories. `known` holds the desktop files that are currently shown. Returns the known
+    /// files that are outdated and the apps that replace them. The app cache is updated as well.
+    pub fn reload_applications(
+        changed: &[PathBuf],
+        known: &[PathBuf],
+        priority: f32,
+        use_keywords: bool,
+    ) -> Result<(HashSet<PathBuf>, Vec<AppData>), SherlockError> {
+        // Installed apps can bring new associations
+        MimeApps::reload();
+        let dirs = get_applications_dir();
+        let entries = get_desktop_entries(&dirs);
+
+        // A change can shadow or uncover a file with the same ID in another directory
+        let affected = |file: &PathBuf| changed.iter().any(|path| file.starts_with(path));
+        let ids: HashSet<String> = known
+            .iter()
+            .chain(entries.values())
+            .filter(|file| affected(file))
+            .chain(changed)
+            .filter_map(|file| desktop_file_id(file, &dirs))
+            .collect();
+        let is_outdated =
+            |file: &Path| desktop_file_id(file, &dirs).is_some_and(|id| ids.contains(&id));
+
+        let files: Vec<PathBuf> = ids
+            .iter()
+            .filter_map(|id| entries.get(id).cloned())
+            .collect();
+        let (counts, decimals) = CounterReader::new()?.counts();
+        let apps = Loader::load_applications_from_disk(
+            Some(files),
+            priority,
+            &counts,
+            decimals,
+            use_keywords,
+        )?;
+        let outdated: HashSet<PathBuf> = known
+            .iter()
+            .filter(|file| is_outdated(file))
+            .cloned()
+            .collect();
+
+        // A missing cache is rebuilt on the next start
+        let config = ConfigGuard::read()?;
+        let cached = config
+            .caching
+            .enable
+            .then(|| BinaryCache::read::<Vec<AppData>, _>(&config.caching.cache).ok())
+            .flatten();
+        if let Some(mut cached) = cached {
+            cached.retain(|app| !app.desktop_file.as_deref().is_some_and(is_outdated));
+            cached.extend(apps.iter().cloned());
+            if let Err(e) = BinaryCache::write(&config.caching.cache, &cached) {
+                let _ = e.insert(false);
+            }
+        }
+        Ok((outdated, apps))
+    }
+
     pub fn load_applications(
         priority: f32,
         counts: &HashMap<String, u32>,
@@ -231,9 +241,15 @@ impl Loader {
             || file_has_changed(&config.files.ignore, &config.caching.cache)
             || file_has_changed(&config.files.config, &config.caching.cache);
 
-        if !changed {
+        // Caches of an older format fail to decode and are read as empty. They are rebuilt
+        // from disk like changed ones.
+        let cached = (!changed)
+            .then(|| BinaryCache::read::<Vec<AppData>, _>(&config.caching.cache).ok())
+            .flatten()
+            .filter(|apps| !apps.is_empty());
+
+        if let Some(mut cached_apps) = cached {
             let _ = sher_log!("Loading cached apps");
-            let mut cached_apps: Vec<AppData> = BinaryCache::read(&config.caching.cache)?;
 
             let cleaned_apps: Vec<AppData> = cached_apps
                 .drain(..)
@@ -288,6 +304,65 @@ impl Loader {
     }
 }
 
+/// Turns the `[Desktop Entry]` group into [`AppData`]. Returns `None` for entries that should
+/// not be shown: hidden ones, ones meant for other desktop environments, ones whose `TryExec`
+/// is missing and types other than `Application` and `Link`.
+fn parse_desktop_entry(
+    entry: &DesktopEntry,
+    path: &Path,
+    locale: Option<&Locale>,
+    desktops: &[String],
+) -> Option<AppData> {
+    let group = entry.group("Desktop Entry")?;
+    if group.boolean("NoDisplay") || group.boolean("Hidden") || !shown_in(&group, desktops) {
+        return None;
+    }
+    if let Some(try_exec) = group.get("TryExec") {
+        if !executable_exists(&try_exec) {
+            return None;
+        }
+    }
+
+    let mut data = AppData::new();
+    data.name = group.localized("Name", locale)?;
+    data.generic_name = group.localized("GenericName", locale);
+    data.comment = group.localized("Comment", locale);
+    data.icon = group.get("Icon");
+    data.search_string = group.localized_list("Keywords", locale).join(";");
+    data.terminal = group.boolean("Terminal");
+    data.exec = match group.get("Type").as_deref() {
+        Some("Application") | None => match group.get("Exec") {
+            Some(exec) => Some(exec),
+            // D-Bus activatable applications are launched by their desktop file id
+            None if group.boolean("DBusActivatable") => path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .map(|id| format!("gapplication launch {}", id)),
+            None => None,
+        },
+        Some("Link") => group
+            .get("URL")
+            .map(|url| format!("xdg-open '{}'", url.replace('\'', "%27"))),
+        Some(_) => return None,
+    };
+
+    // Only actions listed in `Actions` are valid
+    data.actions = group
+        .list("Actions")
+        .iter()
+        .filter_map(|id| {
+            let action_group = entry.group(&format!("Desktop Action {}", id))?;
+            let mut action = ApplicationAction::new("app_launcher");
+            action.name = action_group.localized("Name", locale);
+            action.exec = action_group.get("Exec");
+            action.icon = action_group.get("Icon").or_else(|| data.icon.clone());
+            action.is_valid().then_some(action)
+        })
+        .collect();
+
+    Some(data)
+}
+
 fn should_ignore(ignore_apps: &Vec<Pattern>, app: &str) -> bool {
     let app_name = app.to_lowercase();
     ignore_apps.iter().any(|pattern| pattern.matches(&app_name))
@@ -300,79 +375,121 @@ pub fn parse_priority(priority: f32, count: u32, decimals: i32) -> f32 {
     }
 }
 
-pub fn get_applications_dir() -> HashSet<PathBuf> {
-    let xdg_paths = match env::var("XDG_DATA_DIRS").ok() {
-        Some(paths) => {
-            let app_dirs: HashSet<PathBuf> = paths
-                .split(":")
-                .map(|p| PathBuf::from(p).join("applications/"))
-                .collect();
-            app_dirs
-        }
-        _ => HashSet::new(),
-    };
+/// Data directories in order of precedence as defined by the XDG Base Directory Specification.
+/// `XDG_DATA_HOME` comes first, then `XDG_DATA_DIRS`. Flatpak and Snap export directories are
+/// appended if the environment does not list them.
+pub fn get_data_dirs() -> Vec<PathBuf> {
+    let home = env::var("HOME").map(PathBuf::from).unwrap_or_default();
+    let data_home = env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local/share"));
+    let data_dirs = env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|p| !p.is_empty())
+        .unwrap_or_else(|| String::from("/usr/local/share:/usr/share"));
+
+    let mut dirs = vec![data_home.clone()];
+    dirs.extend(
+        data_dirs
+            .split(':')
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from),
+    );
+    dirs.extend([
+        data_home.join("flatpak/exports/share"),
+        PathBuf::from("/var/lib/flatpak/exports/share"),
+        PathBuf::from("/var/lib/snapd/desktop"),
+    ]);
+    dedup_ordered(dirs)
+}
+
+/// Application directories in order of precedence. Paths from the `app_paths` config take
+/// precedence over the XDG data directories.
+pub fn get_applications_dir() -> Vec<PathBuf> {
     let home = env::var("HOME").ok().unwrap_or("~".to_string());
-    let mut default_paths = vec![
-        String::from("/usr/share/applications/"),
-        String::from("~/.local/share/applications/"),
-    ];
-    if let Ok(c) = ConfigGuard::read() {
-        default_paths.extend(c.debug.app_paths.clone());
-    };
+    let mut paths: Vec<PathBuf> = ConfigGuard::read()
+        .map(|c| {
+            c.debug
+                .app_paths
+                .iter()
+                .map(|path| PathBuf::from(path.replace("~", &home)))
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.extend(
+        get_data_dirs()
+            .into_iter()
+            .map(|dir| dir.join("applications")),
+    );
+    dedup_ordered(paths)
+}
 
-    let mut paths: HashSet<PathBuf> = default_paths
-        .iter()
-        .map(|path| path.replace("~", &home))
-        .map(|path| PathBuf::from(path))
-        .collect();
-    paths.extend(xdg_paths);
+fn dedup_ordered(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut seen = HashSet::with_capacity(paths.len());
     paths
+        .into_iter()
+        .filter(|path| seen.insert(path.clone()))
+        .collect()
 }
 
-pub fn get_desktop_files(mut dirs: HashSet<PathBuf>) -> Vec<PathBuf> {
-    fn read_desktop_dir(dir: PathBuf) -> Option<HashMap<String, PathBuf>> {
-        fs::read_dir(dir).ok().map(|entries| {
-            entries
-                .filter_map(|entry| {
-                    entry.ok().and_then(|f| {
-                        let path = f.path();
-                        let extension = path.extension().and_then(|ext| ext.to_str())?;
-                        if extension == "desktop" {
-                            let stem = path.file_stem().and_then(|s| s.to_str())?;
-                            Some((stem.to_string(), path))
-                        } else {
-                            None
-                        }
-                    })
-                })
-                .collect::<HashMap<String, PathBuf>>()
-        })
+/// Maps desktop file IDs to their files. The ID is the path relative to the applications
+/// directory with `/` replaced by `-`, e.g. `kde/okular.desktop` becomes `kde-okular.desktop`.
+/// If several directories contain the same ID, the one listed first wins.
+pub fn get_desktop_entries(dirs: &[PathBuf]) -> HashMap<String, PathBuf> {
+    fn read_desktop_dir(dir: &Path, prefix: &str, depth: u8, entries: &mut Vec<(String, PathBuf)>) {
+        let Ok(read_dir) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if path.is_dir() {
+                // Guards against symlink loops
+                if depth < 8 {
+                    let prefix = format!("{}{}-", prefix, name);
+                    read_desktop_dir(&path, &prefix, depth + 1, entries);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "desktop") {
+                entries.push((format!("{}{}", prefix, name), path));
+            }
+        }
     }
-    let local_dir = dirs
-        .iter()
-        .find(|p| {
-            p.ends_with(".local/share/applications") || p.ends_with(".local/share/applications/")
+
+    let scanned: Vec<Vec<(String, PathBuf)>> = dirs
+        .par_iter()
+        .map(|dir| {
+            let mut entries = Vec::new();
+            read_desktop_dir(dir, "", 0, &mut entries);
+            entries
         })
-        .cloned();
+        .collect();
 
-    if let Some(local_dir) = &local_dir {
-        dirs.remove(local_dir);
+    let mut entries = HashMap::new();
+    for (id, path) in scanned.into_iter().flatten() {
+        entries.entry(id).or_insert(path);
     }
+    entries
+}
 
-    let mut dirs: HashMap<String, PathBuf> = dirs
-        .into_par_iter()
-        .filter(|dir| dir.is_dir())
-        .filter_map(|dir| read_desktop_dir(dir))
-        .flatten()
-        .collect();
-
-    if let Some(local_dir) = local_dir.and_then(|d| read_desktop_dir(d)) {
-        for (name, path) in local_dir {
-            dirs.insert(name, path);
-        }
+/// The desktop file ID of a file inside one of `dirs`
+pub fn desktop_file_id(file: &Path, dirs: &[PathBuf]) -> Option<String> {
+    if !file.extension().is_some_and(|ext| ext == "desktop") {
+        return None;
     }
+    let relative = dirs.iter().find_map(|dir| file.strip_prefix(dir).ok())?;
+    let parts: Vec<&str> = relative
+        .components()
+        .map(|part| part.as_os_str().to_str())
+        .collect::<Option<_>>()?;
+    Some(parts.join("-"))
+}
 
-    dirs.into_values().collect()
+pub fn get_desktop_files(dirs: Vec<PathBuf>) -> Vec<PathBuf> {
+    get_desktop_entries(&dirs).into_values().collect()
 }
 
 pub fn file_has_changed(file_path: &Path, compare_to: &Path) -> bool {
@@ -418,3 +535,53 @@ impl PathHelpers for Path {
         self.metadata().ok().and_then(|m| m.modified().ok())
     }
 }
+
+#[test]
+fn test_desktop_file_precedence() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/xdg");
+    let dirs: Vec<PathBuf> = ["data_home", "local", "usr", "flatpak/exports/share"]
+        .iter()
+        .map(|dir| root.join(dir).join("applications"))
+        .collect();
+    let entries = get_desktop_entries(&dirs);
+    let source = |id: &str| {
+        entries
+            .get(id)
+            .and_then(|path| path.strip_prefix(&root).ok())
+            .and_then(|path| path.to_str())
+    };
+
+    assert_eq!(entries.len(), 6);
+    // User entries shadow local and system ones
+    assert_eq!(
+        source("firefox.desktop"),
+        Some("data_home/applications/firefox.desktop")
+    );
+    assert_eq!(
+        source("gimp.desktop"),
+        Some("local/applications/gimp.desktop")
+    );
+    // Subdirectories are part of the ID
+    assert_eq!(
+        source("kde-okular.desktop"),
+        Some("data_home/applications/kde/okular.desktop")
+    );
+    assert_eq!(
+        source("kde-plasma-settings.desktop"),
+        Some("usr/applications/kde/plasma/settings.desktop")
+    );
+    // A hidden user entry removes the system one
+    assert_eq!(
+        source("removed.desktop"),
+        Some("data_home/applications/removed.desktop")
+    );
+    assert_eq!(
+        source("org.example.App.desktop"),
+        Some("flatpak/exports/share/applications/org.example.App.desktop")
+    );
+    assert_eq!(
+        desktop_file_id(&dirs[0].join("kde/okular.desktop"), &dirs).as_deref(),
+        Some("kde-okular.desktop")
+    );
+    assert_eq!(desktop_file_id(&root.join("okular.desktop"), &dirs), None);
+}
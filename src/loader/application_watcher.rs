@@ -0,0 +1,141 @@
+use nix::errno::Errno;
+use nix::sys::inotify::{AddWatchFlags, InitFlags, Inotify, WatchDescriptor};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use super::application_loader::get_applications_dir;
+use crate::utils::errors::{SherlockError, SherlockErrorType};
+use crate::{sher_log, sherlock_error};
+
+/// Watches the application directories and their subdirectories with inotify. Directories
+/// that do not exist yet are picked up once they are created.
+pub struct ApplicationWatcher {
+    inotify: Inotify,
+    /// Watched application directories
+    dirs: HashMap<WatchDescriptor, PathBuf>,
+    /// Application directories that do not exist yet
+    pending: Vec<PathBuf>,
+}
+
+impl ApplicationWatcher {
+    const DIR_FLAGS: AddWatchFlags = AddWatchFlags::IN_CREATE
+        .union(AddWatchFlags::IN_CLOSE_WRITE)
+        .union(AddWatchFlags::IN_MOVED_TO)
+        .union(AddWatchFlags::IN_MOVED_FROM)
+        .union(AddWatchFlags::IN_DELETE)
+        .union(AddWatchFlags::IN_ONLYDIR);
+
+    /// Starts watching in a background thread. The paths of changed desktop files and
+    /// directories are sent through `sender`.
+    pub fn spawn(sender: async_channel::Sender<Vec<PathBuf>>) -> Result<(), SherlockError> {
+        let inotify = Inotify::init(InitFlags::IN_CLOEXEC).map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::DirWatchError(String::from("applications")),
+                e.to_string()
+            )
+        })?;
+        let mut watcher = Self {
+            inotify,
+            dirs: HashMap::new(),
+            pending: get_applications_dir(),
+        };
+        watcher.watch_pending();
+
+        thread::spawn(move || watcher.run(sender));
+        Ok(())
+    }
+
+    fn run(mut self, sender: async_channel::Sender<Vec<PathBuf>>) {
+        loop {
+            let events = match self.inotify.read_events() {
+                Ok(events) => events,
+                Err(Errno::EINTR) => continue,
+                Err(e) => {
+                    let _ = sher_log!(format!("Stopped watching applications: {}", e));
+                    return;
+                }
+            };
+
+            let mut changed = Vec::new();
+            for event in events {
+                if event.mask.contains(AddWatchFlags::IN_IGNORED) {
+                    self.dirs.remove(&event.wd);
+                    continue;
+                }
+                let Some(name) = event.name else {
+                    continue;
+                };
+                let dir = self.dirs.get(&event.wd).cloned();
+                let is_dir = event.mask.contains(AddWatchFlags::IN_ISDIR);
+                if is_dir
+                    && event
+                        .mask
+                        .intersects(AddWatchFlags::IN_CREATE | AddWatchFlags::IN_MOVED_TO)
+                {
+                    if let Some(dir) = &dir {
+                        self.watch(&dir.join(&name), 0);
+                    }
+                    if !self.pending.is_empty() {
+                        changed.extend(self.watch_pending());
+                    }
+                }
+                // Events of parents of missing application directories end here
+                let Some(dir) = dir else {
+                    continue;
+                };
+                let path = dir.join(name);
+                if is_dir || path.extension().is_some_and(|ext| ext == "desktop") {
+                    changed.push(path);
+                }
+            }
+            if !changed.is_empty() && sender.send_blocking(changed).is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Watches application directories that exist by now. Missing ones are awaited through
+    /// their closest existing parent. Returns the directories that became available.
+    fn watch_pending(&mut self) -> Vec<PathBuf> {
+        let (available, pending): (Vec<PathBuf>, Vec<PathBuf>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|dir| dir.is_dir());
+        for dir in &available {
+            self.watch(dir, 0);
+        }
+        for dir in &pending {
+            if let Some(parent) = dir.ancestors().skip(1).find(|p| p.is_dir()) {
+                // Uses the same flags in case the parent is an application directory too
+                let _ = self.inotify.add_watch(parent, Self::DIR_FLAGS);
+            }
+        }
+        self.pending = pending;
+        available
+    }
+
+    fn watch(&mut self, dir: &Path, depth: u8) {
+        match self.inotify.add_watch(dir, Self::DIR_FLAGS) {
+            Ok(wd) => {
+                self.dirs.insert(wd, dir.to_path_buf());
+            }
+            Err(e) => {
+                let _ = sher_log!(format!("Failed to watch {}: {}", dir.display(), e));
+                return;
+            }
+        }
+        // Guards against symlink loops
+        if depth >= 8 {
+            return;
+        }
+        if let Ok(read_dir) = fs::read_dir(dir) {
+            for entry in read_dir.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    self.watch(&path, depth + 1);
+                }
+            }
+        }
+    }
+}
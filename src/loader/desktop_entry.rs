@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use crate::sherlock_error;
+use crate::utils::errors::{SherlockError, SherlockErrorType};
+
+/// A `.desktop` file parsed according to the Desktop Entry Specification.
+///
+/// Keys are case sensitive and values are stored as written. Escape sequences are resolved
+/// when a value is read.
+#[derive(Debug, Default)]
+pub struct DesktopEntry {
+    groups: Vec<(String, HashMap<String, String>)>,
+}
+impl DesktopEntry {
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, SherlockError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::FileReadError(path.to_path_buf()),
+                e.to_string()
+            )
+        })?;
+        Ok(Self::parse(&content))
+    }
+    pub fn parse(content: &str) -> Self {
+        let mut groups: Vec<(String, HashMap<String, String>)> = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                groups.push((name.to_string(), HashMap::new()));
+                continue;
+            }
+            // Entries before the first group header are invalid
+            let Some((_, entries)) = groups.last_mut() else {
+                continue;
+            };
+            if let Some((key, value)) = line.split_once('=') {
+                // The first occurrence of a key wins
+                entries
+                    .entry(key.trim_end().to_string())
+                    .or_insert_with(|| value.trim_start().to_string());
+            }
+        }
+        Self { groups }
+    }
+    pub fn group(&self, name: &str) -> Option<DesktopGroup<'_>> {
+        self.groups
+            .iter()
+            .find(|(group, _)| group == name)
+            .map(|(_, entries)| DesktopGroup { entries })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DesktopGroup<'a> {
+    entries: &'a HashMap<String, String>,
+}
+impl DesktopGroup<'_> {
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.entries.get(key).map(|value| unescape(value))
+    }
+    /// Picks the best matching `Key[locale]` and falls back to the plain key.
+    pub fn localized(&self, key: &str, locale: Option<&Locale>) -> Option<String> {
+        self.localized_raw(key, locale).map(unescape)
+    }
+    pub fn list(&self, key: &str) -> Vec<String> {
+        self.entries
+            .get(key)
+            .map(|value| split_list(value))
+            .unwrap_or_default()
+    }
+    pub fn localized_list(&self, key: &str, locale: Option<&Locale>) -> Vec<String> {
+        self.localized_raw(key, locale)
+            .map(split_list)
+            .unwrap_or_default()
+    }
+    pub fn boolean(&self, key: &str) -> bool {
+        self.entries.get(key).is_some_and(|value| value == "true")
+    }
+    fn localized_raw(&self, key: &str, locale: Option<&Locale>) -> Option<&str> {
+        locale
+            .into_iter()
+            .flat_map(Locale::candidates)
+            .find_map(|candidate| self.entries.get(&format!("{}[{}]", key, candidate)))
+            .or_else(|| self.entries.get(key))
+            .map(String::as_str)
+    }
+}
+
+/// The message locale in the form `lang_COUNTRY.ENCODING@MODIFIER`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    lang: String,
+    country: Option<String>,
+    modifier: Option<String>,
+}
+impl Locale {
+    /// Reads the locale from `LC_ALL`, `LC_MESSAGES` or `LANG`, in that order.
+    pub fn from_env() -> Option<Self> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::parse(&value))
+    }
+    pub fn parse(value: &str) -> Option<Self> {
+        let (rest, modifier) = match value.split_once('@') {
+            Some((rest, modifier)) => (rest, Some(modifier.to_string())),
+            None => (value, None),
+        };
+        let rest = rest.split('.').next().unwrap_or_default();
+        let (lang, country) = match rest.split_once('_') {
+            Some((lang, country)) => (lang, Some(country.to_string())),
+            None => (rest, None),
+        };
+        if lang.is_empty() || lang == "C" || lang == "POSIX" {
+            return None;
+        }
+        Some(Self {
+            lang: lang.to_string(),
+            country,
+            modifier,
+        })
+    }
+    /// Locale keys in the order the specification tries them
+    fn candidates(&self) -> Vec<String> {
+        let mut candidates = Vec::with_capacity(4);
+        if let (Some(country), Some(modifier)) = (&self.country, &self.modifier) {
+            candidates.push(format!("{}_{}@{}", self.lang, country, modifier));
+        }
+        if let Some(country) = &self.country {
+            candidates.push(format!("{}_{}", self.lang, country));
+        }
+        if let Some(modifier) = &self.modifier {
+            candidates.push(format!("{}@{}", self.lang, modifier));
+        }
+        candidates.push(self.lang.clone());
+        candidates
+    }
+}
+
+/// Desktop names from `XDG_CURRENT_DESKTOP`
+pub fn current_desktops() -> Vec<String> {
+    env::var("XDG_CURRENT_DESKTOP")
+        .map(|value| {
+            value
+                .split(':')
+                .filter(|desktop| !desktop.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Applies `OnlyShowIn` and `NotShowIn` to the current desktop environment
+pub fn shown_in(group: &DesktopGroup, desktops: &[String]) -> bool {
+    let only = group.list("OnlyShowIn");
+    if !only.is_empty() && !desktops.iter().any(|d| only.contains(d)) {
+        return false;
+    }
+    let not = group.list("NotShowIn");
+    !desktops.iter().any(|d| not.contains(d))
+}
+
+/// Checks whether a `TryExec` value points to an executable, either as an absolute path or
+/// through `PATH`.
+pub fn executable_exists(program: &str) -> bool {
+    fn is_executable(path: &Path) -> bool {
+        path.metadata()
+            .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    if program.contains('/') {
+        return is_executable(Path::new(program));
+    }
+    env::var_os("PATH")
+        .is_some_and(|paths| env::split_paths(&paths).any(|dir| is_executable(&dir.join(program))))
+}
+
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => result.push(' '),
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some('\\') => result.push('\\'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Splits a `;` separated list. `\;` is kept as a literal semicolon.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(';') => current.push(';'),
+                Some(other) => {
+                    current.push('\\');
+                    current.push(other);
+                }
+                None => current.push('\\'),
+            },
+            ';' => items.push(unescape(&std::mem::take(&mut current))),
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        items.push(unescape(&current));
+    }
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+#[test]
+fn test_desktop_entry() {
+    let entry = DesktopEntry::parse(
+        "# comment\n\
+         [Desktop Entry]\n\
+         Type=Application\n\
+         Name=Files\n\
+         Name[de]=Dateien\n\
+         Name[de_AT]=Dateien (AT)\n\
+         Comment=Access\\sand organize files\n\
+         Keywords=folder;manager;semi\\;colon;\n\
+         Keywords[de]=Ordner;Verwaltung;\n\
+         OnlyShowIn=GNOME;Unity;\n\
+         NoDisplay = true\n\
+         [Desktop Action new-window]\n\
+         Name=New Window\n",
+    );
+    let group = entry.group("Desktop Entry").unwrap();
+    let de = Locale::parse("de_DE.UTF-8").unwrap();
+    let at = Locale::parse("de_AT@euro").unwrap();
+
+    assert_eq!(group.localized("Name", None).as_deref(), Some("Files"));
+    assert_eq!(
+        group.localized("Name", Some(&de)).as_deref(),
+        Some("Dateien")
+    );
+    assert_eq!(
+        group.localized("Name", Some(&at)).as_deref(),
+        Some("Dateien (AT)")
+    );
+    assert_eq!(
+        group.get("Comment").as_deref(),
+        Some("Access and organize files")
+    );
+    assert_eq!(
+        group.list("Keywords"),
+        vec!["folder", "manager", "semi;colon"]
+    );
+    assert_eq!(
+        group.localized_list("Keywords", Some(&de)),
+        vec!["Ordner", "Verwaltung"]
+    );
+    assert!(group.boolean("NoDisplay"));
+    assert!(group.get("name").is_none());
+    assert!(Locale::parse("C.UTF-8").is_none());
+
+    assert!(shown_in(&group, &[String::from("GNOME")]));
+    assert!(!shown_in(&group, &[String::from("KDE")]));
+    assert!(!shown_in(&group, &[]));
+
+    let action = entry.group("Desktop Action new-window").unwrap();
+    assert_eq!(action.get("Name").as_deref(), Some("New Window"));
+}
@@ -2,7 +2,7 @@ use std::{env, path::PathBuf, str::FromStr};
 
 use super::Loader;
 use crate::utils::{
-    config::{SherlockConfig, SherlockFlags},
+    config::{OutputFormat, SherlockConfig, SherlockFlags},
     errors::SherlockError,
 };
 
@@ -81,6 +81,7 @@ impl SherlockFlags {
             sub_menu: Self::extract_flag_value::<String>(&args, "--sub-menu", Some("-sm")),
             method: Self::extract_flag_value::<String>(&args, "--method", None),
             field: Self::extract_flag_value::<String>(&args, "--field", None),
+            output_format: Self::extract_flag_value::<OutputFormat>(&args, "--output-format", None),
             multi: check_flag_existence("--multi"),
             photo_mode: check_flag_existence("--photo"),
             input: Self::extract_flag_value::<bool>(&args, "--input", None),
@@ -148,6 +149,10 @@ pub fn flag_documentation() -> Result<(), SherlockError> {
             "--field",
             "Specifies which of your fields should be printed on return press",
         ),
+        (
+            "--output-format",
+            "Sets how selected rows are printed. Can be \"newline\", \"null\" or \"json\"",
+        ),
     ];
     let longest = allowed_flags
         .iter()
@@ -10,18 +10,27 @@ use std::fs;
 use std::fs::File;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
+use crate::actions::applaunch::split_as_command;
 use crate::actions::util::read_from_clipboard;
 use crate::launcher::audio_launcher::AudioLauncherFunctions;
 use crate::launcher::bookmark_launcher::BookmarkLauncher;
-use crate::launcher::calc_launcher::{CalculatorLauncher, Currency, CURRENCIES};
+use crate::launcher::calc_history_launcher::CalcHistoryLauncher;
+use crate::launcher::calc_launcher::{CalculatorLauncher, Currency, CurrencyProvider, CURRENCIES};
 use crate::launcher::category_launcher::CategoryLauncher;
 use crate::launcher::emoji_picker::{EmojiPicker, SkinTone};
 use crate::launcher::event_launcher::EventLauncher;
 use crate::launcher::file_launcher::FileLauncher;
+use crate::launcher::generate_launcher::GenerateLauncher;
+use crate::launcher::most_used_launcher::MostUsedLauncher;
+use crate::launcher::plugin_launcher::PluginLauncher;
 use crate::launcher::pomodoro_launcher::{Pomodoro, PomodoroStyle};
 use crate::launcher::process_launcher::ProcessLauncher;
+use crate::launcher::recent_files_launcher::RecentFilesLauncher;
+use crate::launcher::script_launcher::ScriptLauncher;
 use crate::launcher::theme_picker::ThemePicker;
+use crate::launcher::transform_launcher::{Transform, TransformLauncher};
 use crate::launcher::weather_launcher::{WeatherIconTheme, WeatherLauncher};
 use crate::launcher::{
     app_launcher, bulk_text_launcher, clipboard_launcher, system_cmd_launcher, web_launcher,
@@ -30,20 +39,21 @@ use crate::launcher::{
 use crate::loader::util::CounterReader;
 use crate::ui::tiles::calc_tile::CalcTileHandler;
 use crate::utils::cache::BinaryCache;
-use crate::utils::config::{ConfigGuard, ConstantDefaults};
+use crate::utils::config::{ConfigCaching, ConfigGuard, ConstantDefaults};
 use crate::utils::errors::SherlockError;
 use crate::utils::errors::SherlockErrorType;
 use crate::utils::files::{expand_path, home_dir};
 
 use app_launcher::AppLauncher;
 use bulk_text_launcher::BulkTextLauncher;
-use clipboard_launcher::ClipboardLauncher;
+use clipboard_launcher::{color, ClipboardLauncher};
 use simd_json;
 use simd_json::prelude::ArrayTrait;
 use system_cmd_launcher::CommandLauncher;
 use web_launcher::WebLauncher;
 
 use super::application_loader::parse_priority;
+use super::mime_apps::MimeApps;
 use super::util::deserialize_named_appdata;
 use super::util::AppData;
 use super::util::RawLauncher;
@@ -51,12 +61,14 @@ use super::Loader;
 use crate::sherlock_error;
 
 pub static COLOR_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"^(rgb|hsl)*\(?(\d{1,3}\s*,\s*\d{1,3}\s*,\s*\d{1,3})\)?|\(?(\s*\d{1,3}\s*,\s*\d{1,3}%\s*,\s*\d{1,3}\s*%\w*)\)?|^#([a-fA-F0-9]{6,8})$").unwrap()
+    Regex::new(r"(?i)^(?:(?P<func>rgba?|hsla?|hsv|hsb|oklch|cmyk)\s*\(?\s*(?P<args>[^()]+?)\s*\)?|\(?\s*(?P<bare>\d{1,3}\s*,\s*\d{1,3}%?\s*,\s*\d{1,3}%?)\s*\)?|#(?P<hex>[a-f0-9]{3,4}|[a-f0-9]{6}|[a-f0-9]{8}))$").unwrap()
 });
 
 impl Loader {
     #[sherlock_macro::timing(name = "Loading launchers")]
     pub fn load_launchers() -> Result<(Vec<Launcher>, Vec<SherlockError>), SherlockError> {
+        // Default apps might have changed since the last reload
+        MimeApps::reload();
         let config = ConfigGuard::read()?;
 
         // Read fallback data here:
@@ -64,13 +76,7 @@ impl Loader {
 
         // Read cached counter file
         let counter_reader = CounterReader::new()?;
-        let counts: HashMap<String, u32> =
-            BinaryCache::read(&counter_reader.path).unwrap_or_default();
-        let max_decimals = counts
-            .iter()
-            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
-            .map(|(_, v)| v.to_string().len())
-            .unwrap_or(0) as i32;
+        let (counts, max_decimals) = counter_reader.counts();
 
         let submenu = config
             .runtime
@@ -78,7 +84,7 @@ impl Loader {
             .clone()
             .unwrap_or(String::from("all"));
         // Parse the launchers
-        let launchers: Vec<Launcher> = raw_launchers
+        let mut launchers: Vec<Launcher> = raw_launchers
             .into_par_iter()
             .filter_map(|raw| {
                 // Logic to restrict in submenu mode
@@ -99,16 +105,23 @@ impl Loader {
                     }
                     "bulk_text" => parse_bulk_text_launcher(&raw),
                     "calculation" => parse_calculator(&raw),
+                    "calc_history" => parse_calc_history_launcher(&raw),
                     "categories" => parse_category_launcher(&raw, &counts, max_decimals),
                     "clipboard-execution" => parse_clipboard_launcher(&raw).ok()?,
                     "command" => parse_command_launcher(&raw, &counts, max_decimals),
                     "debug" => parse_debug_launcher(&raw, &counts, max_decimals),
                     "emoji_picker" => parse_emoji_launcher(&raw),
                     "files" => parse_file_launcher(&raw),
+                    "generate" => parse_generate_launcher(&raw),
+                    "most_used" => parse_most_used_launcher(&raw),
                     "teams_event" => parse_event_launcher(&raw),
                     "theme_picker" => parse_theme_launcher(&raw),
+                    "transform" => parse_transform_launcher(&raw),
                     "process" => parse_process_launcher(&raw),
+                    "plugin" => parse_plugin_launcher(&raw),
                     "pomodoro" => parse_pomodoro(&raw),
+                    "recent_files" => parse_recent_files_launcher(&raw),
+                    "script" => parse_script_launcher(&raw),
                     "weather" => parse_weather_launcher(&raw),
                     "web_launcher" => parse_web_launcher(&raw),
                     _ => LauncherType::Empty,
@@ -126,6 +139,7 @@ impl Loader {
                 Some(Launcher::from_raw(raw, method, launcher_type, icon))
             })
             .collect();
+        fill_most_used_apps(&mut launchers, &counts, max_decimals, &config.caching);
 
         // Get errors and launchers
         let mut non_breaking = Vec::new();
@@ -175,6 +189,11 @@ fn parse_app_launcher(
         .get("use_keywords")
         .and_then(|s| s.as_bool())
         .unwrap_or(true);
+    let customize = raw
+        .args
+        .get("customize")
+        .and_then(|s| s.as_bool())
+        .unwrap_or(true);
     let apps = match caching {
         true => Loader::load_applications(raw.priority, counts, max_decimals, use_keywords),
         false => Loader::load_applications_from_disk(
@@ -186,7 +205,12 @@ fn parse_app_launcher(
         ),
     }
     .unwrap_or_default();
-    LauncherType::App(AppLauncher { apps })
+    LauncherType::App(AppLauncher {
+        apps,
+        priority: raw.priority,
+        use_keywords,
+        customize,
+    })
 }
 #[sherlock_macro::timing(level = "launchers")]
 fn parse_audio_sink_launcher() -> LauncherType {
@@ -240,8 +264,14 @@ fn parse_bulk_text_launcher(raw: &RawLauncher) -> LauncherType {
             .args
             .get("exec-args")
             .and_then(Value::as_str)
-            .unwrap_or_default()
-            .to_string(),
+            .map(split_as_command)
+            .unwrap_or_default(),
+        timeout: parse_timeout(raw),
+        persistent: raw
+            .args
+            .get("persistent")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
     })
 }
 fn parse_calculator(raw: &RawLauncher) -> LauncherType {
@@ -259,10 +289,21 @@ fn parse_calculator(raw: &RawLauncher) -> LauncherType {
         .get("currency_update_interval")
         .and_then(|interval| interval.as_u64())
         .unwrap_or(60 * 60 * 24);
+    let providers: Vec<CurrencyProvider> = match raw.args.get("currency_providers") {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or_else(|e| {
+            let _result = sherlock_error!(SherlockErrorType::DeserializationError, e.to_string())
+                .insert(false);
+            CurrencyProvider::defaults()
+        }),
+        None => CurrencyProvider::defaults(),
+    };
 
     idle_add(move || {
+        let providers = providers.clone();
         MainContext::default().spawn_local(async move {
-            let result = Currency::get_exchange(update_interval).await.ok();
+            let result = Currency::get_exchange(&providers, update_interval)
+                .await
+                .ok();
             let _result = CURRENCIES.set(result);
         });
         false.into()
@@ -271,6 +312,11 @@ fn parse_calculator(raw: &RawLauncher) -> LauncherType {
     LauncherType::Calc(CalculatorLauncher { capabilities })
 }
 #[sherlock_macro::timing(level = "launchers")]
+fn parse_calc_history_launcher(raw: &RawLauncher) -> LauncherType {
+    let limit = raw.args.get("limit").and_then(Value::as_u64).unwrap_or(100) as usize;
+    LauncherType::CalcHistory(CalcHistoryLauncher::new(raw.priority, limit))
+}
+#[sherlock_macro::timing(level = "launchers")]
 fn parse_category_launcher(
     raw: &RawLauncher,
     counts: &HashMap<String, u32>,
@@ -318,14 +364,15 @@ fn parse_clipboard_launcher(raw: &RawLauncher) -> Result<LauncherType, SherlockE
             }
         }
 
+        let mut colors = vec![];
         if !has_val
             && capabilities
                 .iter()
                 .find(|c| c.starts_with("colors."))
                 .is_some()
-            && clipboard_content.len() <= 20
         {
-            has_val = COLOR_RE.is_match(&clipboard_content);
+            colors = color::tiles(&clipboard_content, &capabilities);
+            has_val = !colors.is_empty();
         }
 
         if !has_val
@@ -345,6 +392,7 @@ fn parse_clipboard_launcher(raw: &RawLauncher) -> Result<LauncherType, SherlockE
         Ok(LauncherType::Clipboard(ClipboardLauncher {
             clipboard_content,
             capabilities: capabilities.clone(),
+            colors,
         }))
     }
 }
@@ -360,6 +408,74 @@ fn parse_command_launcher(
     LauncherType::Command(CommandLauncher { commands })
 }
 
+#[sherlock_macro::timing(level = "launchers")]
+fn parse_plugin_launcher(raw: &RawLauncher) -> LauncherType {
+    let plugin = PluginLauncher {
+        exec: raw
+            .args
+            .get("exec")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        args: raw
+            .args
+            .get("exec-args")
+            .and_then(Value::as_str)
+            .map(split_as_command)
+            .unwrap_or_default(),
+        icon: raw
+            .args
+            .get("icon")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        timeout: parse_timeout(raw),
+        settings: raw.args.get("settings").cloned().unwrap_or_default(),
+    };
+
+    // let running plugins know that sherlock reloaded
+    let refresh = plugin.clone();
+    idle_add(move || {
+        refresh.refresh();
+        false.into()
+    });
+
+    LauncherType::Plugin(plugin)
+}
+#[sherlock_macro::timing(level = "launchers")]
+fn parse_script_launcher(raw: &RawLauncher) -> LauncherType {
+    let name = raw.name.clone().unwrap_or(String::from("script"));
+    // Scripts are either inlined using `source` or read from the file at `script`
+    let (name, source) = if let Some(source) = raw.args.get("source").and_then(Value::as_str) {
+        (name, source.to_string())
+    } else if let Some(path) = raw.args.get("script").and_then(Value::as_str) {
+        let path = match home_dir() {
+            Ok(home) => expand_path(path, &home),
+            Err(err) => {
+                let _result = err.insert(false);
+                return LauncherType::Empty;
+            }
+        };
+        match fs::read_to_string(&path) {
+            Ok(source) => (path.display().to_string(), source),
+            Err(e) => {
+                let _result =
+                    sherlock_error!(SherlockErrorType::FileReadError(path), e.to_string())
+                        .insert(false);
+                return LauncherType::Empty;
+            }
+        }
+    } else {
+        return LauncherType::Empty;
+    };
+
+    match ScriptLauncher::compile(name.clone(), &source) {
+        Ok(script) => LauncherType::Script(script),
+        Err(e) => {
+            let _result = sherlock_error!(SherlockErrorType::ScriptError(name), e).insert(false);
+            LauncherType::Empty
+        }
+    }
+}
 #[sherlock_macro::timing(level = "launchers")]
 fn parse_pomodoro(raw: &RawLauncher) -> LauncherType {
     let home = match home_dir() {
@@ -495,6 +611,114 @@ fn parse_process_launcher(raw: &RawLauncher) -> LauncherType {
     }
 }
 #[sherlock_macro::timing(level = "launchers")]
+fn parse_recent_files_launcher(raw: &RawLauncher) -> LauncherType {
+    let limit = raw.args.get("limit").and_then(Value::as_u64).unwrap_or(50) as usize;
+    match RecentFilesLauncher::new(raw, limit) {
+        Ok(launcher) => LauncherType::RecentFiles(launcher),
+        Err(err) => {
+            let _result = err.insert(false);
+            LauncherType::Empty
+        }
+    }
+}
+#[sherlock_macro::timing(level = "launchers")]
+fn parse_most_used_launcher(raw: &RawLauncher) -> LauncherType {
+    let limit = raw.args.get("limit").and_then(Value::as_u64).unwrap_or(6) as usize;
+    let window = raw.args.get("window").and_then(Value::as_u64);
+    let icon_size = raw
+        .args
+        .get("icon_size")
+        .and_then(Value::as_i64)
+        .unwrap_or(48) as i32;
+    // Filled in by `fill_most_used_apps` once all launchers are parsed
+    LauncherType::MostUsed(MostUsedLauncher {
+        apps: vec![],
+        limit,
+        window,
+        icon_size,
+    })
+}
+/// Most used grids pick from the apps of the app launcher. Without one, the app cache is
+/// read, but never written, so it is not updated twice at the same time.
+fn fill_most_used_apps(
+    launchers: &mut [Launcher],
+    counts: &HashMap<String, u32>,
+    max_decimals: i32,
+    caching: &ConfigCaching,
+) {
+    if !launchers
+        .iter()
+        .any(|l| matches!(l.launcher_type, LauncherType::MostUsed(_)))
+    {
+        return;
+    }
+    let apps = launchers
+        .iter()
+        .find_map(|l| match &l.launcher_type {
+            LauncherType::App(app) => Some(app.apps.clone()),
+            _ => None,
+        })
+        .or_else(|| {
+            caching
+                .enable
+                .then(|| BinaryCache::read::<Vec<AppData>, _>(&caching.cache).ok())
+                .flatten()
+                .filter(|apps| !apps.is_empty())
+        })
+        .map_or_else(
+            || Loader::load_applications_from_disk(None, 0.0, counts, max_decimals, false),
+            Ok,
+        );
+    let apps = match apps {
+        Ok(apps) => apps,
+        Err(e) => {
+            let _result = e.insert(false);
+            vec![]
+        }
+    };
+    for launcher in launchers.iter_mut() {
+        if let LauncherType::MostUsed(most_used) = &mut launcher.launcher_type {
+            most_used.apps = apps.clone();
+        }
+    }
+}
+#[sherlock_macro::timing(level = "launchers")]
+fn parse_generate_launcher(raw: &RawLauncher) -> LauncherType {
+    let password_length = raw
+        .args
+        .get("password_length")
+        .and_then(Value::as_u64)
+        .unwrap_or(20) as usize;
+    LauncherType::Generate(GenerateLauncher { password_length })
+}
+#[sherlock_macro::timing(level = "launchers")]
+fn parse_transform_launcher(raw: &RawLauncher) -> LauncherType {
+    let operations: Vec<Transform> = match raw.args.get("operations") {
+        Some(Value::Array(arr)) => arr
+            .iter()
+            .filter_map(Value::as_str)
+            .filter_map(|id| {
+                let operation = Transform::from_id(id);
+                if operation.is_none() {
+                    let _result = sherlock_error!(
+                        SherlockErrorType::DeserializationError,
+                        format!("Unknown transform operation \"{}\"", id)
+                    )
+                    .insert(false);
+                }
+                operation
+            })
+            .collect(),
+        _ => Transform::ALL.to_vec(),
+    };
+    // The clipboard is used whenever the query is empty
+    let clipboard = read_from_clipboard().unwrap_or_default();
+    LauncherType::Transform(TransformLauncher {
+        operations,
+        clipboard,
+    })
+}
+#[sherlock_macro::timing(level = "launchers")]
 fn parse_weather_launcher(raw: &RawLauncher) -> LauncherType {
     if let Some(location) = raw.args.get("location").and_then(Value::as_str) {
         let update_interval = raw
@@ -564,6 +788,35 @@ fn parse_web_launcher(raw: &RawLauncher) -> LauncherType {
     })
 }
 
+/// The `timeout` argument in seconds. Invalid and non-positive values fall back to two
+/// seconds.
+fn parse_timeout(raw: &RawLauncher) -> Duration {
+    let default = Duration::from_secs(2);
+    let Some(secs) = raw.args.get("timeout").and_then(Value::as_f64) else {
+        return default;
+    };
+    match Duration::try_from_secs_f64(secs) {
+        Ok(timeout) if !timeout.is_zero() => timeout,
+        result => {
+            let reason = result.err().map_or_else(
+                || String::from("must be greater than zero"),
+                |e| e.to_string(),
+            );
+            let _result = sherlock_error!(
+                SherlockErrorType::ConfigError(Some(format!(
+                    "Invalid timeout \"{}\" for launcher \"{}\": {}",
+                    secs,
+                    raw.name.as_deref().unwrap_or(&raw.r#type),
+                    reason
+                ))),
+                ""
+            )
+            .insert(false);
+            default
+        }
+    }
+}
+
 #[sherlock_macro::timing(name = "Parsing launcher config")]
 fn parse_launcher_configs(
     fallback_path: &PathBuf,
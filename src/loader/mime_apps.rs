@@ -0,0 +1,271 @@
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use super::application_loader::{get_applications_dir, get_desktop_entries};
+use super::desktop_entry::{current_desktops, DesktopEntry, Locale};
+use super::util::ApplicationAction;
+use crate::actions::applaunch::to_path;
+
+/// Associations of the current session, loaded on first use and reloaded together with the
+/// launchers and applications
+pub static MIME_APPS: Lazy<RwLock<MimeApps>> = Lazy::new(|| RwLock::new(MimeApps::load()));
+
+/// Resolves applications for MIME types as described in the XDG MIME Applications
+/// Specification, using `mimeapps.list` and `mimeinfo.cache` files.
+#[derive(Debug, Default)]
+pub struct MimeApps {
+    /// `mimeapps.list` files in order of precedence
+    lists: Vec<DesktopEntry>,
+    /// `mimeinfo.cache` files in order of precedence
+    caches: Vec<DesktopEntry>,
+    /// Installed desktop files by desktop file ID
+    entries: HashMap<String, PathBuf>,
+    locale: Option<Locale>,
+}
+
+/// An application that can open a MIME type
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesktopApp {
+    pub id: String,
+    pub name: String,
+    pub exec: String,
+    pub icon: Option<String>,
+    pub terminal: bool,
+}
+
+impl MimeApps {
+    pub fn load() -> Self {
+        let home = env::var("HOME").map(PathBuf::from).unwrap_or_default();
+        let config_home = env::var("XDG_CONFIG_HOME")
+            .ok()
+            .filter(|p| !p.is_empty())
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".config"));
+        let config_dirs = env::var("XDG_CONFIG_DIRS")
+            .ok()
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| String::from("/etc/xdg"));
+
+        let mut dirs = vec![config_home];
+        dirs.extend(
+            config_dirs
+                .split(':')
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from),
+        );
+        Self::from_dirs(
+            &dirs,
+            &get_applications_dir(),
+            &current_desktops(),
+            Locale::from_env(),
+        )
+    }
+    /// Re-reads the associations if they were loaded already. Otherwise they are loaded on
+    /// first use anyway.
+    pub fn reload() {
+        if let Some(lock) = Lazy::get(&MIME_APPS) {
+            let apps = Self::load();
+            if let Ok(mut current) = lock.write() {
+                *current = apps;
+            }
+        }
+    }
+
+    /// `config_dirs` and `app_dirs` are expected in order of precedence
+    pub fn from_dirs(
+        config_dirs: &[PathBuf],
+        app_dirs: &[PathBuf],
+        desktops: &[String],
+        locale: Option<Locale>,
+    ) -> Self {
+        // Desktop specific lists take precedence in every directory
+        let file_names: Vec<String> = desktops
+            .iter()
+            .map(|desktop| format!("{}-mimeapps.list", desktop.to_lowercase()))
+            .chain([String::from("mimeapps.list")])
+            .collect();
+        let lists = config_dirs
+            .iter()
+            .chain(app_dirs)
+            .flat_map(|dir| file_names.iter().map(move |name| dir.join(name)))
+            .filter_map(|path| DesktopEntry::read(path).ok())
+            .collect();
+        let caches = app_dirs
+            .iter()
+            .filter_map(|dir| DesktopEntry::read(dir.join("mimeinfo.cache")).ok())
+            .collect();
+
+        Self {
+            lists,
+            caches,
+            entries: get_desktop_entries(app_dirs),
+            locale,
+        }
+    }
+
+    /// The preferred application for a MIME type
+    pub fn default_app(&self, mime: &str) -> Option<DesktopApp> {
+        self.lists
+            .iter()
+            .filter_map(|list| list.group("Default Applications"))
+            .flat_map(|group| group.list(mime))
+            .find_map(|id| self.app(&id))
+            .or_else(|| self.associated(mime).into_iter().next())
+    }
+
+    /// Every application that can open a MIME type, starting with the default one
+    pub fn apps(&self, mime: &str) -> Vec<DesktopApp> {
+        let mut apps: Vec<DesktopApp> = self.default_app(mime).into_iter().collect();
+        for app in self.associated(mime) {
+            if !apps.iter().any(|a| a.id == app.id) {
+                apps.push(app);
+            }
+        }
+        apps
+    }
+
+    /// Added associations and cached `MimeType` entries. Removed associations only hide
+    /// entries of lower precedence.
+    fn associated(&self, mime: &str) -> Vec<DesktopApp> {
+        let mut removed: HashSet<String> = HashSet::new();
+        let mut ids: Vec<String> = Vec::new();
+        for list in &self.lists {
+            if let Some(group) = list.group("Added Associations") {
+                ids.extend(
+                    group
+                        .list(mime)
+                        .into_iter()
+                        .filter(|id| !removed.contains(id)),
+                );
+            }
+            if let Some(group) = list.group("Removed Associations") {
+                removed.extend(group.list(mime));
+            }
+        }
+        for cache in &self.caches {
+            if let Some(group) = cache.group("MIME Cache") {
+                ids.extend(
+                    group
+                        .list(mime)
+                        .into_iter()
+                        .filter(|id| !removed.contains(id)),
+                );
+            }
+        }
+
+        let mut seen = HashSet::new();
+        ids.into_iter()
+            .filter(|id| seen.insert(id.clone()))
+            .filter_map(|id| self.app(&id))
+            .collect()
+    }
+
+    fn app(&self, id: &str) -> Option<DesktopApp> {
+        let entry = DesktopEntry::read(self.entries.get(id)?).ok()?;
+        let group = entry.group("Desktop Entry")?;
+        if group.boolean("Hidden") {
+            return None;
+        }
+        Some(DesktopApp {
+            id: id.to_string(),
+            name: group.localized("Name", self.locale.as_ref())?,
+            exec: group.get("Exec")?,
+            icon: group.get("Icon"),
+            terminal: group.boolean("Terminal"),
+        })
+    }
+}
+
+/// The MIME type of a file path, a `file://` URL or any other URL. URLs map to
+/// `x-scheme-handler/<scheme>`.
+pub fn mime_type(target: &str) -> Option<String> {
+    let scheme = target
+        .split_once(':')
+        .map(|(scheme, _)| scheme)
+        .filter(|s| {
+            s.len() > 1
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        });
+    match scheme {
+        Some(scheme) if !scheme.eq_ignore_ascii_case("file") => {
+            Some(format!("x-scheme-handler/{}", scheme.to_lowercase()))
+        }
+        _ => {
+            let path = to_path(target);
+            let path = Path::new(&path);
+            if path.is_dir() {
+                return Some(String::from("inode/directory"));
+            }
+            let (content_type, _) = gio::content_type_guess(Some(path), &[]);
+            gio::content_type_get_mime_type(&content_type).map(|mime| mime.to_string())
+        }
+    }
+}
+
+/// Context actions to open a file or URL with every application that supports it
+pub fn open_with_actions(target: &str) -> Vec<ApplicationAction> {
+    let Some(mime) = mime_type(target) else {
+        return vec![];
+    };
+    let apps = MIME_APPS
+        .read()
+        .map(|apps| apps.apps(&mime))
+        .unwrap_or_default();
+    apps.into_iter()
+        .map(|app| {
+            let mut action = ApplicationAction::new("app_launcher");
+            action.name = Some(format!("Open with {}", app.name));
+            action.exec = Some(app.exec);
+            action.icon = app.icon;
+            action.terminal = app.terminal;
+            action.target = Some(target.to_string());
+            action
+        })
+        .collect()
+}
+
+#[test]
+fn test_mime_apps() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/mime");
+    let apps = MimeApps::from_dirs(
+        &[root.join("config")],
+        &[root.join("data/applications")],
+        &[String::from("GNOME")],
+        None,
+    );
+    let default = |mime: &str| apps.default_app(mime).map(|app| app.id);
+    let ids = |mime: &str| {
+        apps.apps(mime)
+            .into_iter()
+            .map(|app| app.id)
+            .collect::<Vec<_>>()
+    };
+
+    // Uninstalled defaults are skipped
+    assert_eq!(
+        default("x-scheme-handler/https").as_deref(),
+        Some("chromium.desktop")
+    );
+    // Desktop specific lists take precedence
+    assert_eq!(default("text/html").as_deref(), Some("firefox.desktop"));
+    // The user configuration takes precedence over system lists
+    assert_eq!(default("image/png").as_deref(), Some("gimp.desktop"));
+    // Without a default, the first association is used
+    assert_eq!(default("text/plain").as_deref(), Some("gedit.desktop"));
+    assert_eq!(default("application/pdf"), None);
+
+    assert_eq!(
+        ids("x-scheme-handler/https"),
+        vec!["chromium.desktop", "firefox.desktop"]
+    );
+    // Removed associations are hidden
+    assert_eq!(ids("image/png"), vec!["gimp.desktop", "eog.desktop"]);
+
+    let gimp = apps.default_app("image/png").unwrap();
+    assert_eq!(gimp.name, "GNU Image Manipulation Program");
+    assert_eq!(gimp.exec, "gimp-2.10 %U");
+}
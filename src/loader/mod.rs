@@ -1,8 +1,12 @@
 pub mod application_loader;
+pub mod application_watcher;
 pub mod css_loader;
+pub mod desktop_entry;
 pub mod flag_loader;
 pub mod icon_loader;
 pub mod launcher_loader;
+pub mod mime_apps;
+pub mod pins;
 pub mod pipe_loader;
 pub mod resource_loader;
 pub mod util;
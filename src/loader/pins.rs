@@ -0,0 +1,117 @@
+use once_cell::sync::Lazy;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use crate::utils::errors::{SherlockError, SherlockErrorType};
+use crate::utils::paths;
+use crate::{sher_log, sherlock_error};
+
+/// Pinned items of the current session, loaded on first use
+pub static PINS: Lazy<RwLock<Pins>> = Lazy::new(|| RwLock::new(Pins::load()));
+
+/// Items pinned to the home view, stored in order as keys in `pins.json` inside the data
+/// directory. Keys look like `app:firefox.desktop`, `bookmark:<url>`, `command:<exec>` or
+/// `pipe:<id>`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Pins {
+    keys: Vec<String>,
+}
+
+impl Pins {
+    fn path() -> Result<PathBuf, SherlockError> {
+        Ok(paths::get_data_dir()?.join("pins.json"))
+    }
+
+    pub fn load() -> Self {
+        let Ok(path) = Self::path() else {
+            return Self::default();
+        };
+        let keys = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                let _ = sher_log!(format!("Failed to parse {}: {}", path.display(), e));
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        Self { keys }
+    }
+
+    pub fn save(&self) -> Result<(), SherlockError> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                sherlock_error!(
+                    SherlockErrorType::DirCreateError(parent.to_string_lossy().to_string()),
+                    e.to_string()
+                )
+            })?;
+        }
+        let content = serde_json::to_string_pretty(&self.keys).map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::FileWriteError(path.clone()),
+                e.to_string()
+            )
+        })?;
+        fs::write(&path, content)
+            .map_err(|e| sherlock_error!(SherlockErrorType::FileWriteError(path), e.to_string()))
+    }
+
+    /// Position of the item among the pinned ones
+    pub fn position(&self, key: &str) -> Option<usize> {
+        self.keys.iter().position(|k| k == key)
+    }
+
+    /// Pins the item at the end, or unpins it. Returns whether it is pinned now.
+    pub fn toggle(&mut self, key: &str) -> bool {
+        match self.position(key) {
+            Some(i) => {
+                self.keys.remove(i);
+                false
+            }
+            None => {
+                self.keys.push(key.to_string());
+                true
+            }
+        }
+    }
+
+    /// Moves a pinned item up (negative `offset`) or down. Returns whether it moved.
+    pub fn shift(&mut self, key: &str, offset: isize) -> bool {
+        let Some(from) = self.position(key) else {
+            return false;
+        };
+        let to = from
+            .saturating_add_signed(offset)
+            .min(self.keys.len().saturating_sub(1));
+        if from == to {
+            return false;
+        }
+        let key = self.keys.remove(from);
+        self.keys.insert(to, key);
+        true
+    }
+}
+
+#[test]
+fn test_pins() {
+    let mut pins = Pins::default();
+    assert!(pins.toggle("app:firefox.desktop"));
+    assert!(pins.toggle("bookmark:https://example.com"));
+    assert!(pins.toggle("pipe:notes"));
+    assert_eq!(pins.position("pipe:notes"), Some(2));
+
+    assert!(pins.shift("pipe:notes", -1));
+    assert_eq!(pins.position("pipe:notes"), Some(1));
+    assert!(pins.shift("pipe:notes", -5));
+    assert_eq!(pins.position("pipe:notes"), Some(0));
+    // Already at the top
+    assert!(!pins.shift("pipe:notes", -1));
+    assert!(pins.shift("app:firefox.desktop", 1));
+    assert_eq!(pins.position("app:firefox.desktop"), Some(2));
+    assert!(!pins.shift("command:missing", 1));
+
+    assert!(!pins.toggle("pipe:notes"));
+    assert_eq!(pins.position("pipe:notes"), None);
+    assert_eq!(pins.position("bookmark:https://example.com"), Some(0));
+}
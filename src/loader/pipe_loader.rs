@@ -32,6 +32,8 @@ impl Loader {
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct PipedElements {
+    /// Identifies the element across runs, e.g. to pin it
+    pub id: Option<String>,
     pub title: Option<String>,
     pub description: Option<String>,
     pub icon: Option<String>,
@@ -167,6 +169,7 @@ impl PipedData {
                     .unwrap_or_default();
 
                 result.push(PipedElements {
+                    id: None,
                     title: Some(name.clone()),
                     description: None,
                     result: Some(name),
@@ -181,6 +184,7 @@ impl PipedData {
             } else {
                 // If it's not valid UTF-8, treat it as binary data
                 result.push(PipedElements {
+                    id: None,
                     title: None,
                     description: None,
                     result: None,
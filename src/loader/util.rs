@@ -10,6 +10,7 @@ use std::{
     fs::{self, File},
     hash::{Hash, Hasher},
     path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use crate::{
@@ -76,6 +77,11 @@ pub struct ApplicationAction {
     pub method: String,
     #[serde(default = "default_true")]
     pub exit: bool,
+    /// File or URL passed to the app, e.g. for "Open with" actions
+    #[serde(default)]
+    pub target: Option<String>,
+    #[serde(default)]
+    pub terminal: bool,
 }
 impl ApplicationAction {
     pub fn new(method: &str) -> Self {
@@ -85,6 +91,8 @@ impl ApplicationAction {
             icon: None,
             method: method.to_string(),
             exit: true,
+            target: None,
+            terminal: false,
         }
     }
     pub fn is_valid(&self) -> bool {
@@ -99,7 +107,14 @@ impl ApplicationAction {
 pub struct AppData {
     #[serde(default)]
     pub name: String,
+    #[serde(default)]
+    pub generic_name: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
     pub exec: Option<String>,
+    /// File or URL passed to `exec`, e.g. for recent files
+    #[serde(default)]
+    pub target: Option<String>,
     pub search_string: String,
     #[serde(default)]
     pub priority: f32,
@@ -120,7 +135,10 @@ impl AppData {
     pub fn new() -> Self {
         Self {
             name: String::new(),
+            generic_name: None,
+            comment: None,
             exec: None,
+            target: None,
             search_string: String::new(),
             priority: 0.0,
             icon: None,
@@ -149,7 +167,10 @@ impl AppData {
             .to_string();
         Self {
             name: name_string.clone(),
+            generic_name: None,
+            comment: None,
             exec: path,
+            target: None,
             search_string: name_string,
             priority: raw.priority,
             icon: Some(icon),
@@ -173,7 +194,10 @@ impl AppData {
         );
         let mut data = Self {
             name: raw.name.clone().unwrap_or_default(),
+            generic_name: None,
+            comment: None,
             exec: Default::default(),
+            target: None,
             search_string,
             priority: raw.priority,
             icon: None,
@@ -211,11 +235,9 @@ impl AppData {
                 self.icon = Some(alias_icon.to_string());
             }
             if let Some(alias_keywords) = alias.keywords.as_ref() {
-                self.search_string =
-                    Self::construct_search(&self.name, &alias_keywords, use_keywords);
+                self.search_string = self.construct_search(alias_keywords, use_keywords);
             } else {
-                self.search_string =
-                    Self::construct_search(&self.name, &self.search_string, use_keywords);
+                self.search_string = self.construct_search(&self.search_string, use_keywords);
             }
             if let Some(alias_exec) = alias.exec.as_ref() {
                 self.exec = Some(alias_exec.to_string());
@@ -243,16 +265,25 @@ impl AppData {
                 self.vars.extend(variables);
             }
         } else {
-            self.search_string =
-                Self::construct_search(&self.name, &self.search_string, use_keywords);
+            self.search_string = self.construct_search(&self.search_string, use_keywords);
         }
     }
-    fn construct_search(name: &str, search_str: &str, use_keywords: bool) -> String {
+    /// The generic name is always searchable, the comment only together with the keywords
+    fn construct_search(&self, search_str: &str, use_keywords: bool) -> String {
+        let mut search = self.name.clone();
+        if let Some(generic_name) = &self.generic_name {
+            search.push(';');
+            search.push_str(generic_name);
+        }
         if use_keywords {
-            format!("{};{}", name, search_str)
-        } else {
-            name.to_string()
+            if let Some(comment) = &self.comment {
+                search.push(';');
+                search.push_str(comment);
+            }
+            search.push(';');
+            search.push_str(search_str);
         }
+        search
     }
 }
 impl Eq for AppData {}
@@ -260,6 +291,7 @@ impl Hash for AppData {
     fn hash<H: Hasher>(&self, state: &mut H) {
         // Make more efficient and handle error using f32
         self.exec.hash(state);
+        self.target.hash(state);
         self.desktop_file.hash(state);
     }
 }
@@ -304,11 +336,17 @@ pub struct SherlockAlias {
 
 pub struct CounterReader {
     pub path: PathBuf,
+    /// Launch times in seconds since the epoch, used to count executions within a time window
+    pub usage_path: PathBuf,
 }
 impl CounterReader {
+    /// Launch times kept per key
+    const MAX_LAUNCHES: usize = 100;
+
     pub fn new() -> Result<Self, SherlockError> {
         let data_dir = paths::get_data_dir()?;
         let path = data_dir.join("counts.bin");
+        let usage_path = data_dir.join("usage.bin");
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent).map_err(|e| {
                 sherlock_error!(
@@ -317,7 +355,17 @@ impl CounterReader {
                 )
             })?;
         }
-        Ok(CounterReader { path })
+        Ok(CounterReader { path, usage_path })
+    }
+    /// Execution counts together with the number of decimals the highest count needs
+    pub fn counts(&self) -> (HashMap<String, u32>, i32) {
+        let counts: HashMap<String, u32> = BinaryCache::read(&self.path).unwrap_or_default();
+        let max_decimals = counts
+            .iter()
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, v)| v.to_string().len())
+            .unwrap_or(0) as i32;
+        (counts, max_decimals)
     }
     pub fn increment(&self, key: &str) -> Result<(), SherlockError> {
         let mut content: HashMap<String, u32> = BinaryCache::read(&self.path)?;
@@ -338,8 +386,39 @@ impl CounterReader {
 
         *content.entry(key.to_string()).or_insert(0) += 1;
         BinaryCache::write(&self.path, &content)?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let mut usage: HashMap<String, Vec<u64>> =
+            BinaryCache::read(&self.usage_path).unwrap_or_default();
+        Self::record_launch(&mut usage, key, now);
+        BinaryCache::write(&self.usage_path, &usage)?;
         Ok(())
     }
+    /// Number of executions since `since`, in seconds since the epoch
+    pub fn counts_since(&self, since: u64) -> HashMap<String, u32> {
+        let usage: HashMap<String, Vec<u64>> =
+            BinaryCache::read(&self.usage_path).unwrap_or_default();
+        Self::count_since(&usage, since)
+    }
+    fn record_launch(usage: &mut HashMap<String, Vec<u64>>, key: &str, time: u64) {
+        let launches = usage.entry(key.to_string()).or_default();
+        launches.push(time);
+        if launches.len() > Self::MAX_LAUNCHES {
+            launches.drain(..launches.len() - Self::MAX_LAUNCHES);
+        }
+    }
+    fn count_since(usage: &HashMap<String, Vec<u64>>, since: u64) -> HashMap<String, u32> {
+        usage
+            .iter()
+            .map(|(key, launches)| {
+                let count = launches.iter().filter(|&&time| time >= since).count();
+                (key.clone(), count as u32)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
 }
 
 pub struct JsonCache;
@@ -375,3 +454,24 @@ impl JsonCache {
             .map_err(|e| sherlock_error!(SherlockErrorType::DeserializationError, e.to_string()))
     }
 }
+
+#[test]
+fn test_usage_counts() {
+    let mut usage = HashMap::new();
+    for time in [100, 200, 300] {
+        CounterReader::record_launch(&mut usage, "firefox", time);
+    }
+    CounterReader::record_launch(&mut usage, "gimp", 150);
+
+    let counts = CounterReader::count_since(&usage, 150);
+    assert_eq!(counts.get("firefox"), Some(&2));
+    assert_eq!(counts.get("gimp"), Some(&1));
+    assert_eq!(CounterReader::count_since(&usage, 250).get("gimp"), None);
+
+    // Only the most recent launches are kept
+    for time in 0..CounterReader::MAX_LAUNCHES as u64 {
+        CounterReader::record_launch(&mut usage, "gimp", 1000 + time);
+    }
+    assert_eq!(usage["gimp"].len(), CounterReader::MAX_LAUNCHES);
+    assert_eq!(usage["gimp"][0], 1000);
+}
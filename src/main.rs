@@ -202,10 +202,15 @@ async fn main() {
         }
 
         // Spawn api listener
+        let search_handler = sherlock.borrow().search_handler.clone();
         let _server = SherlockServer::listen(sherlock);
 
         // Logic for handling the daemonization
         if setup.config.runtime.daemonize {
+            // Pick up installed and removed apps without a restart
+            if let Some(handler) = search_handler {
+                handler.watch_applications();
+            }
             // Used to cache render
             if let Some(window) = open_win.upgrade() {
                 let _ = gtk4::prelude::WidgetExt::activate_action(&window, "win.close", None);
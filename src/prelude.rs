@@ -282,14 +282,23 @@ impl SherlockNav for ListView {
     }
     fn execute_by_index(&self, index: u32) {
         if let Some(selection) = self.model().and_downcast::<SingleSelection>() {
-            if let Some(item_at_index) = (0..selection.n_items())
+            if let Some((item_at_index, cell, cells)) = (0..selection.n_items())
                 .filter_map(|i| selection.item(i).and_downcast::<TileItem>())
-                .filter(|item| item.shortcut().is_some())
+                .flat_map(|item| {
+                    let cells = item.shortcuts().len();
+                    (0..cells).map(move |cell| (item.clone(), cell, cells))
+                })
                 .nth(index as usize)
             {
                 let exit: u8 = 0;
+                // Tiles with several shortcuts get the position of the cell
+                let callback = if cells > 1 {
+                    cell.to_string()
+                } else {
+                    String::new()
+                };
                 if let Some(row) = item_at_index.parent().upgrade() {
-                    row.emit_by_name::<()>("row-should-activate", &[&exit, &""]);
+                    row.emit_by_name::<()>("row-should-activate", &[&exit, &callback]);
                 }
             }
         }
@@ -106,19 +106,12 @@ impl EventPort {
                     self.key_actions.arg_prev();
                 }
 
+                // Falls back to the selected row if nothing is marked
                 UIFunction::Exec => {
-                    if !ConfigGuard::read().map_or(false, |c| c.runtime.multi) {
-                        self.key_actions.on_return(None);
-                    } else {
-                        self.key_actions.on_multi_return(None);
-                    }
+                    self.key_actions.on_multi_return(None);
                 }
                 UIFunction::ExecInplace => {
-                    if !ConfigGuard::read().map_or(false, |c| c.runtime.multi) {
-                        self.key_actions.on_return(Some(false));
-                    } else {
-                        self.key_actions.on_multi_return(Some(false));
-                    }
+                    self.key_actions.on_multi_return(Some(false));
                 }
 
                 UIFunction::MultiSelect => {
@@ -165,6 +158,13 @@ impl EventPort {
                     }
                 }
 
+                UIFunction::PinUp => {
+                    self.key_actions.move_pin(-1);
+                }
+                UIFunction::PinDown => {
+                    self.key_actions.move_pin(1);
+                }
+
                 UIFunction::ErrorPage => {
                     let api_call = ApiCall::SwitchMode(crate::api::api::SherlockModes::Error);
                     let _ = SherlockServer::send_action(api_call);
@@ -290,6 +290,9 @@ pub enum UIFunction {
     ClearBar,
     Backspace,
 
+    PinUp,
+    PinDown,
+
     ErrorPage,
 
     Shortcut,
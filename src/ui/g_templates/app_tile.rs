@@ -10,6 +10,9 @@ mod imp {
         #[template_child(id = "app-name")]
         pub title: TemplateChild<Label>,
 
+        #[template_child(id = "app-subtitle")]
+        pub subtitle: TemplateChild<Label>,
+
         #[template_child(id = "launcher-type")]
         pub category: TemplateChild<Label>,
 
@@ -12,6 +12,9 @@ mod imp {
 
         #[template_child(id = "result-holder")]
         pub result_holder: TemplateChild<Label>,
+
+        #[template_child(id = "info-holder")]
+        pub info_holder: TemplateChild<Label>,
     }
 
     #[glib::object_subclass]
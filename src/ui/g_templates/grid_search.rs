@@ -13,6 +13,9 @@ mod imp {
         #[template_child(id = "preview_box")]
         pub preview_box: TemplateChild<GtkBox>,
 
+        #[template_child(id = "search-bar-holder")]
+        pub search_bar_holder: TemplateChild<GtkBox>,
+
         #[template_child(id = "search-bar")]
         pub search_bar: TemplateChild<Entry>,
 
@@ -1,6 +1,7 @@
 use gio::{
     glib::{
         object::{Cast, CastNone, ObjectExt},
+        variant::ToVariant,
         WeakRef,
     },
     prelude::ListModelExt,
@@ -11,22 +12,25 @@ use gtk4::{
 };
 use gtk4::{
     prelude::{EditableExt, WidgetExt},
-    Entry, GridView, ListView, SingleSelection,
+    Entry, GridView, ListScrollFlags, ListView, SingleSelection,
 };
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
 use crate::{
-    actions::{execute_from_attrs, get_attrs_map},
+    actions::{execute_from_attrs, get_attrs_map, util::format_output},
+    daemon::daemon::print_reponse,
     g_subclasses::{
         action_entry::ContextAction, emoji_action_entry::EmojiContextAction,
         emoji_item::EmojiObject, sherlock_row::SherlockRow, tile_item::TileItem,
     },
     launcher::emoji_picker::SkinTone,
+    loader::pins::PINS,
     prelude::SherlockNav,
     ui::{
         g_templates::{ArgBar, SearchUiObj},
         search::UserBindHandler,
     },
+    utils::config::ConfigGuard,
 };
 
 use super::util::ContextUI;
@@ -57,8 +61,9 @@ impl KeyActions {
         }
     }
     pub fn on_multi_return(&self, close: Option<bool>) {
-        // no context menu yet
+        // context actions always apply to the selected row only
         if self.context.open.get() {
+            self.on_return(close);
             return;
         }
         if let Some(actives) = self
@@ -71,8 +76,10 @@ impl KeyActions {
                 self.on_return(close);
                 return;
             }
+            // Only the last row is allowed to close the window
+            let last: u8 = close.map_or(0, |v| if v { 2 } else { 1 });
             actives.into_iter().enumerate().for_each(|(i, row)| {
-                let exit: u8 = if i < len - 1 { 1 } else { 0 };
+                let exit: u8 = if i < len - 1 { 1 } else { last };
                 row.emit_by_name::<()>("row-should-activate", &[&exit, &""]);
             });
         }
@@ -115,13 +122,24 @@ impl KeyActions {
                 }
             } else {
                 if let Some(current_text) = self.search_bar.upgrade().map(|s| s.text()) {
-                    println!("{}", current_text);
+                    let output = format_output(&current_text, &HashMap::new());
+                    let _result = print_reponse(output);
                 }
             }
         }
     }
     pub fn mark_active(&self) {
         if let Some(results) = self.results.upgrade() {
+            // Outside of multi mode, only rows that can be bulk-executed are markable
+            if !ConfigGuard::read().map_or(false, |c| c.runtime.multi) {
+                let bulk = results
+                    .selected_item()
+                    .and_downcast::<TileItem>()
+                    .map_or(false, |item| item.supports_bulk());
+                if !bulk {
+                    return;
+                }
+            }
             results.mark_active();
         }
     }
@@ -193,6 +211,30 @@ impl KeyActions {
         );
         Some(())
     }
+    /// Moves the selected item up (negative `offset`) or down among the pinned ones and keeps
+    /// it selected
+    pub fn move_pin(&self, offset: isize) -> Option<()> {
+        let results = self.results.upgrade()?;
+        let selection = results.model().and_downcast::<SingleSelection>()?;
+        let item = selection.selected_item().and_downcast::<TileItem>()?;
+        let key = item.pin_key()?;
+        {
+            let mut pins = PINS.write().ok()?;
+            if !pins.shift(&key, offset) {
+                return None;
+            }
+            if let Err(error) = pins.save() {
+                let _result = error.insert(false);
+            }
+        }
+        let _ = results.activate_action("win.update-items", Some(&false.to_variant()));
+
+        let index = (0..selection.n_items())
+            .find(|i| selection.item(*i).and_downcast::<TileItem>().as_ref() == Some(&item))?;
+        selection.set_selected(index);
+        results.scroll_to(index, ListScrollFlags::NONE, None);
+        Some(())
+    }
 
     // ---- PRIVATES ----
     fn move_prev(&self) -> Option<()> {
@@ -4,13 +4,14 @@ use gio::{
 };
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 use gtk4::{
-    self, prelude::*, CustomFilter, CustomSorter, EventControllerKey, FilterListModel, Overlay,
-    SignalListItemFactory, SingleSelection, SortListModel, Widget,
+    self, prelude::*, CustomFilter, CustomSorter, EventControllerKey, FilterListModel, Label,
+    ListHeader, Overlay, SignalListItemFactory, SingleSelection, SortListModel, Widget,
 };
 use gtk4::{glib, ApplicationWindow};
 use levenshtein::levenshtein;
 use simd_json::prelude::ArrayTrait;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::{cell::RefCell, f32};
 
@@ -105,7 +106,7 @@ pub fn search(
                                         row.add_css_class("animate");
                                     }
                                 }
-                                if let Some(shortcut) = item.shortcut() {
+                                for shortcut in item.shortcuts() {
                                     if current < num_shortcuts + 1 {
                                         current += shortcut.apply_shortcut(current, &modstr);
                                     } else {
@@ -237,7 +238,7 @@ pub fn search(
                             let mut current = 1;
                             for i in 0..selection.n_items() {
                                 if let Some(item) = selection.item(i).and_downcast::<TileItem>() {
-                                    if let Some(shortcut) = item.shortcut() {
+                                    for shortcut in item.shortcuts() {
                                         if current < num_shortcuts + 1 {
                                             current += shortcut.apply_shortcut(current, &modstr);
                                         } else {
@@ -276,7 +277,49 @@ pub fn search(
             }
         })
         .build();
-    window.add_action_entries([mode_action, action_clear_win, sorter_actions]);
+    let action_set_search = ActionEntry::builder("set-search")
+        .parameter_type(Some(&String::static_variant_type()))
+        .activate({
+            let search_bar = imp.search_bar.downgrade();
+            move |_: &ApplicationWindow, _, parameter| {
+                let text = parameter
+                    .and_then(|p| p.get::<String>())
+                    .unwrap_or_default();
+                let search_bar = search_bar.clone();
+                glib::idle_add_local(move || {
+                    if let Some(entry) = search_bar.upgrade() {
+                        entry.set_text(&text);
+                        entry.set_position(-1);
+                        entry.grab_focus();
+                    }
+                    glib::ControlFlow::Break
+                });
+            }
+        })
+        .build();
+    // Action to re-read an app after it was customized
+    let action_reload_apps = ActionEntry::builder("reload-apps")
+        .parameter_type(Some(&String::static_variant_type()))
+        .activate({
+            let model = handler.model.clone();
+            move |_: &ApplicationWindow, _, parameter| {
+                let file = parameter.and_then(|p| p.get::<String>());
+                if let (Some(model), Some(file)) = (model.as_ref().and_then(|m| m.upgrade()), file)
+                {
+                    glib::MainContext::default().spawn_local(async move {
+                        update_applications(&model, vec![PathBuf::from(file)]).await;
+                    });
+                }
+            }
+        })
+        .build();
+    window.add_action_entries([
+        mode_action,
+        action_clear_win,
+        action_set_search,
+        sorter_actions,
+        action_reload_apps,
+    ]);
 
     return Ok(stack_page);
 }
@@ -335,11 +378,16 @@ fn construct_window(
     imp.results.set_factory(Some(&factory));
 
     // Setup selection
-    let sorter = make_sorter(&search_text);
+    let sorter = make_sorter(&search_text, &mode);
     let filter = make_filter(&search_text, &mode);
     let filter_model = FilterListModel::new(Some(model.clone()), Some(filter.clone()));
     let sorted_model = SortListModel::new(Some(filter_model), Some(sorter.clone()));
 
+    // Pinned items form their own section at the top of the home view
+    sorted_model.set_section_sorter(Some(&make_section_sorter(&search_text, &mode)));
+    imp.results
+        .set_header_factory(Some(&make_header_factory(&search_text, &mode)));
+
     let selection = SingleSelection::new(Some(sorted_model));
     imp.results.set_model(Some(&selection));
 
@@ -422,13 +470,57 @@ fn make_factory(search_text: Rc<RefCell<String>>) -> SignalListItemFactory {
             row.remove(&child);
         }
 
-        if let Some(shortcut) = tile_item.shortcut() {
+        for shortcut in tile_item.shortcuts() {
             shortcut.remove_shortcut();
         }
         tile_item.set_parent(None);
     });
     factory
 }
+/// Position of a pinned item, if the home view is shown
+fn home_pin_index(
+    item: &TileItem,
+    search_text: &Rc<RefCell<String>>,
+    mode: &Rc<RefCell<String>>,
+) -> Option<usize> {
+    let is_home = search_text.borrow().is_empty() && mode.borrow().trim() == "all";
+    if !is_home {
+        return None;
+    }
+    item.pin_index()
+}
+fn make_header_factory(
+    search_text: &Rc<RefCell<String>>,
+    mode: &Rc<RefCell<String>>,
+) -> SignalListItemFactory {
+    let factory = SignalListItemFactory::new();
+    factory.connect_setup(|_, header| {
+        let header = header
+            .downcast_ref::<ListHeader>()
+            .expect("Header must be a ListHeader");
+        let label = Label::new(Some("Pinned"));
+        label.set_xalign(0.0);
+        label.add_css_class("pinned-header");
+        header.set_child(Some(&label));
+    });
+    factory.connect_bind({
+        let search_text = Rc::clone(search_text);
+        let mode = Rc::clone(mode);
+        move |_, header| {
+            let header = header
+                .downcast_ref::<ListHeader>()
+                .expect("Header must be a ListHeader");
+            let pinned = header
+                .item()
+                .and_downcast::<TileItem>()
+                .is_some_and(|item| home_pin_index(&item, &search_text, &mode).is_some());
+            if let Some(label) = header.child() {
+                label.set_visible(pinned);
+            }
+        }
+    });
+    factory
+}
 fn make_filter(search_text: &Rc<RefCell<String>>, mode: &Rc<RefCell<String>>) -> CustomFilter {
     CustomFilter::new({
         let search_text = Rc::clone(search_text);
@@ -451,6 +543,10 @@ fn make_filter(search_text: &Rc<RefCell<String>>, mode: &Rc<RefCell<String>>) ->
             let update_res = item.based_show(&search_text.borrow());
             item.update(&search_text.borrow());
 
+            if is_home && item.pin_index().is_some() {
+                return true;
+            }
+
             if home == HomeType::Persist {
                 if mode != "all" && Some(mode) != launcher.alias {
                     return false;
@@ -485,9 +581,26 @@ fn make_filter(search_text: &Rc<RefCell<String>>, mode: &Rc<RefCell<String>>) ->
         }
     })
 }
-fn make_sorter(search_text: &Rc<RefCell<String>>) -> CustomSorter {
+fn make_section_sorter(
+    search_text: &Rc<RefCell<String>>,
+    mode: &Rc<RefCell<String>>,
+) -> CustomSorter {
+    CustomSorter::new({
+        let search_text = Rc::clone(search_text);
+        let mode = Rc::clone(mode);
+        move |item_a, item_b| {
+            let item_a = item_a.downcast_ref::<TileItem>().unwrap();
+            let item_b = item_b.downcast_ref::<TileItem>().unwrap();
+            let pinned_a = home_pin_index(item_a, &search_text, &mode).is_some();
+            let pinned_b = home_pin_index(item_b, &search_text, &mode).is_some();
+            pinned_b.cmp(&pinned_a).into()
+        }
+    })
+}
+fn make_sorter(search_text: &Rc<RefCell<String>>, mode: &Rc<RefCell<String>>) -> CustomSorter {
     CustomSorter::new({
         let search_text = Rc::clone(search_text);
+        let mode = Rc::clone(mode);
         fn search_score(query: &str, match_in: &str) -> f32 {
             if match_in.len() == 0 {
                 return 0.0;
@@ -537,11 +650,22 @@ fn make_sorter(search_text: &Rc<RefCell<String>>) -> CustomSorter {
             prio.trunc() + (counters + score).min(0.99)
         }
         move |item_a, item_b| {
-            let search_text = search_text.borrow().to_ascii_lowercase();
-
             let item_a = item_a.downcast_ref::<TileItem>().unwrap();
             let item_b = item_b.downcast_ref::<TileItem>().unwrap();
 
+            // Pinned items keep their order
+            match (
+                home_pin_index(item_a, &search_text, &mode),
+                home_pin_index(item_b, &search_text, &mode),
+            ) {
+                (Some(a), Some(b)) => return a.cmp(&b).into(),
+                (Some(_), None) => return gtk4::Ordering::Smaller,
+                (None, Some(_)) => return gtk4::Ordering::Larger,
+                (None, None) => {}
+            }
+
+            let search_text = search_text.borrow().to_ascii_lowercase();
+
             let mut priority_a = item_a.priority();
             let mut priority_b = item_b.priority();
 
@@ -1,5 +1,7 @@
 use gio::glib::object::ObjectExt;
+use gio::glib::property::PropertySet;
 use gio::glib::WeakRef;
+use gio::ListStore;
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 use gtk4::{prelude::*, Widget};
 use std::cell::RefCell;
@@ -9,15 +11,17 @@ use std::vec;
 
 use crate::actions::{execute_from_attrs, get_attrs_map};
 use crate::g_subclasses::sherlock_row::SherlockRow;
-use crate::launcher::bulk_text_launcher::{AsyncCommandResponse, BulkTextLauncher};
+use crate::g_subclasses::tile_item::{TileItem, UpdateHandler};
+use crate::launcher::bulk_text_launcher::{AsyncCommandOutput, AsyncCommandResponse};
 use crate::launcher::Launcher;
 use crate::prelude::{IconComp, TileHandler};
 use crate::ui::g_templates::ApiTile;
+use crate::ui::tiles::pipe_tile::PipeTileHandler;
 
 use super::Tile;
 
 impl Tile {
-    pub fn api(launcher: Rc<Launcher>, api: &BulkTextLauncher) -> ApiTile {
+    pub fn api(launcher: Rc<Launcher>, icon: Option<&str>) -> ApiTile {
         let tile = ApiTile::new();
         let imp = tile.imp();
 
@@ -29,17 +33,32 @@ impl Tile {
         }
 
         // Set icons
-        imp.icon.set_icon(Some(&api.icon), None, None);
+        imp.icon.set_icon(icon, None, None);
         imp.icon.set_pixel_size(15);
 
         tile
     }
+    pub fn async_items(parent: &Launcher, responses: Vec<AsyncCommandResponse>) -> Vec<TileItem> {
+        responses
+            .into_iter()
+            .map(|response| {
+                let launcher = Rc::new(parent.async_child(response));
+                let tile = TileItem::new();
+                let handler = PipeTileHandler::new(launcher.clone());
+                tile.imp().update_handler.set(UpdateHandler::Pipe(handler));
+                tile.set_launcher(launcher);
+                tile
+            })
+            .collect()
+    }
 }
 
 #[derive(Default, Debug)]
 pub struct ApiTileHandler {
     tile: WeakRef<ApiTile>,
     attrs: Rc<RefCell<HashMap<String, String>>>,
+    model: WeakRef<ListStore>,
+    children: Rc<RefCell<Vec<TileItem>>>,
 }
 impl ApiTileHandler {
     pub fn new(launcher: Rc<Launcher>) -> Self {
@@ -50,8 +69,13 @@ impl ApiTileHandler {
         Self {
             tile: WeakRef::new(),
             attrs: Rc::new(RefCell::new(attrs)),
+            model: WeakRef::new(),
+            children: Rc::new(RefCell::new(vec![])),
         }
     }
+    pub fn set_model(&self, model: &ListStore) {
+        self.model.set(Some(model));
+    }
     pub async fn update_async(
         &self,
         keyword: &str,
@@ -63,36 +87,64 @@ impl ApiTileHandler {
 
         imp.content_title.set_text(&keyword);
 
-        if let Some(response) = launcher.get_result(&keyword).await {
-            let AsyncCommandResponse {
-                title,
-                content,
-                next_content,
-                actions,
-                result,
-            } = response;
-            if let Some(title) = title {
-                imp.content_title.set_text(&title);
-            }
-            if let Some(content) = content {
-                imp.content_body.set_markup(&content);
-            }
+        match launcher.get_result(&keyword).await {
+            Some(AsyncCommandOutput::Single(response)) => {
+                self.replace_children(vec![]);
+                let AsyncCommandResponse {
+                    title,
+                    content,
+                    next_content,
+                    actions,
+                    result,
+                    ..
+                } = response;
+                if let Some(title) = title {
+                    imp.content_title.set_text(&title);
+                }
+                if let Some(content) = content {
+                    imp.content_body.set_markup(&content);
+                }
 
-            if let Some(action) = actions {
-                let open = !action.is_empty();
-                let _ = row.activate_action("win.context-mode", Some(&open.to_variant()));
-                row.set_actions(action);
-            }
+                if let Some(action) = actions {
+                    let open = !action.is_empty();
+                    let _ = row.activate_action("win.context-mode", Some(&open.to_variant()));
+                    row.set_actions(action);
+                }
 
-            if let Some(next_content) = next_content {
-                let mut attrs = self.attrs.borrow_mut();
-                attrs.insert(String::from("next_content"), next_content.to_string());
-                attrs.insert(String::from("keyword"), keyword.to_string());
-                result.map(|result| attrs.insert(String::from("result"), result));
+                if let Some(next_content) = next_content {
+                    let mut attrs = self.attrs.borrow_mut();
+                    attrs.insert(String::from("next_content"), next_content.to_string());
+                    attrs.insert(String::from("keyword"), keyword.to_string());
+                    result.map(|result| attrs.insert(String::from("result"), result));
+                }
+            }
+            Some(AsyncCommandOutput::Multiple(responses)) => {
+                imp.content_body
+                    .set_text(&format!("{} results", responses.len()));
+                let children = Tile::async_items(&launcher, responses);
+                self.replace_children(children);
             }
+            None => self.replace_children(vec![]),
         }
         Some(())
     }
+    /// Swaps the tiles of the previous query for the new ones within a single splice, so
+    /// the view never shows a mix of both.
+    fn replace_children(&self, children: Vec<TileItem>) {
+        let Some(model) = self.model.upgrade() else {
+            return;
+        };
+        let mut current = self.children.borrow_mut();
+        if current.is_empty() && children.is_empty() {
+            return;
+        }
+        let (position, removals) = current
+            .first()
+            .and_then(|first| model.find(first))
+            .map_or((model.n_items(), 0), |pos| (pos, current.len() as u32));
+        model.splice(position, removals, &children);
+        *current = children;
+    }
     pub fn change_attrs(&self, key: String, value: String) {
         self.attrs.borrow_mut().insert(key, value);
     }
@@ -65,6 +65,21 @@ impl AppTileHandler {
                 .entry("term".to_string())
                 .and_modify(|val| *val = value.terminal.to_string())
                 .or_insert_with(|| value.terminal.to_string());
+
+            // Values for the %c, %i and %k field codes
+            attrs.insert(String::from("name"), value.name.clone());
+            match &value.icon {
+                Some(icon) => attrs.insert(String::from("icon"), icon.clone()),
+                None => attrs.remove("icon"),
+            };
+            match value.desktop_file.as_ref().and_then(|p| p.to_str()) {
+                Some(path) => attrs.insert(String::from("desktop_file"), path.to_string()),
+                None => attrs.remove("desktop_file"),
+            };
+            match &value.target {
+                Some(target) => attrs.insert(String::from("targets"), target.clone()),
+                None => attrs.remove("targets"),
+            };
         }
 
         let name = value.name.clone();
@@ -85,6 +100,15 @@ impl AppTileHandler {
 
         imp.title.set_text(&tile_name);
 
+        // Prefer the comment since the generic name is often close to the name itself
+        match value.comment.as_ref().or(value.generic_name.as_ref()) {
+            Some(subtitle) => {
+                imp.subtitle.set_text(subtitle);
+                imp.subtitle.set_visible(true);
+            }
+            None => imp.subtitle.set_visible(false),
+        }
+
         if let Some(name) = &launcher.name {
             imp.category.set_text(name);
         } else {
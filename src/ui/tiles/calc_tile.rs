@@ -2,7 +2,16 @@ use super::Tile;
 use crate::{
     actions::{execute_from_attrs, get_attrs_map},
     g_subclasses::sherlock_row::SherlockRow,
-    launcher::{calc_launcher::Calculator, Launcher},
+    launcher::{
+        calc_launcher::{
+            datetime,
+            expression::{self, Evaluation},
+            history::{self, HistoryEntry},
+            programmer, units, Calculator, Currency, CURRENCIES,
+        },
+        Launcher,
+    },
+    loader::util::ApplicationAction,
     prelude::TileHandler,
     ui::g_templates::CalcTile,
 };
@@ -12,7 +21,6 @@ use gio::glib::{
 };
 use gtk4::subclass::prelude::ObjectSubclassIsExt;
 use gtk4::{prelude::WidgetExt, Widget};
-use meval::eval_str;
 use std::{
     cell::RefCell,
     collections::{HashMap, HashSet},
@@ -30,6 +38,12 @@ pub struct CalcTileHandler {
     tile: WeakRef<CalcTile>,
     attrs: Rc<RefCell<HashMap<String, String>>>,
     pub result: RefCell<Option<(String, String)>>,
+    /// Latest math result. Stored as `ans` once the tile is executed.
+    evaluation: Rc<RefCell<Option<Evaluation>>>,
+    /// Source of the exchange rates, shown below currency results
+    info: RefCell<Option<String>>,
+    /// Copy actions for each representation of a programmer conversion
+    actions: RefCell<Vec<ApplicationAction>>,
 }
 impl CalcTileHandler {
     pub fn new(launcher: Rc<Launcher>) -> Self {
@@ -41,6 +55,9 @@ impl CalcTileHandler {
             tile: WeakRef::new(),
             attrs: Rc::new(RefCell::new(attrs)),
             result: RefCell::new(None),
+            evaluation: Rc::new(RefCell::new(None)),
+            info: RefCell::new(None),
+            actions: RefCell::new(vec![]),
         }
     }
     pub fn based_show(&self, keyword: &str, capabilities: &HashSet<String>) -> bool {
@@ -49,33 +66,57 @@ impl CalcTileHandler {
         }
 
         let mut result = None;
+        let mut evaluation = None;
+        let mut info = None;
+        let mut actions = vec![];
 
-        if capabilities.contains("calc.math") {
-            let trimmed_keyword = keyword.trim();
-            if let Ok(r) = eval_str(trimmed_keyword) {
-                let r = r.to_string();
-                if &r != trimmed_keyword {
-                    result = Some((r.clone(), format!("= {}", r)));
-                }
-            }
+        // Dates like `2026-12-24 - 2026-10-01` would otherwise be solved as math
+        if capabilities.contains("calc.datetime") {
+            result = datetime::evaluate(&keyword);
         }
 
-        if (capabilities.contains("calc.lengths") || capabilities.contains("calc.units"))
-            && result.is_none()
-        {
-            result = Calculator::measurement(&keyword, "lengths");
+        if capabilities.contains("calc.programmer") && result.is_none() {
+            if let Some(conversion) = programmer::convert(&keyword) {
+                actions = conversion
+                    .representations
+                    .into_iter()
+                    .map(|(label, value)| ApplicationAction {
+                        name: Some(format!("Copy {}", label)),
+                        exec: Some(value),
+                        icon: Some(String::from("edit-copy")),
+                        method: String::from("copy"),
+                        exit: true,
+                        target: None,
+                        terminal: false,
+                    })
+                    .collect();
+                result = Some((conversion.copy, conversion.display));
+            }
         }
 
-        if (capabilities.contains("calc.weights") || capabilities.contains("calc.units"))
-            && result.is_none()
-        {
-            result = Calculator::measurement(&keyword, "weights");
+        if capabilities.contains("calc.math") && result.is_none() {
+            let trimmed_keyword = keyword.trim();
+            if let Ok(eval) = expression::evaluate(trimmed_keyword) {
+                let r = eval.value.to_string();
+                if &r != trimmed_keyword {
+                    let text = match &eval.assign {
+                        Some(name) => format!("{} = {}", name, r),
+                        None => format!("= {}", r),
+                    };
+                    result = Some((r, text));
+                    evaluation = Some(eval);
+                }
+            }
         }
+        *self.evaluation.borrow_mut() = evaluation;
 
-        if (capabilities.contains("calc.volumes") || capabilities.contains("calc.units"))
-            && result.is_none()
-        {
-            result = Calculator::measurement(&keyword, "volumes");
+        for family in units::FAMILIES {
+            if result.is_some() {
+                break;
+            }
+            if capabilities.contains(family.capability) || capabilities.contains("calc.units") {
+                result = Calculator::measurement(&keyword, family.name);
+            }
         }
 
         if (capabilities.contains("calc.temperatures") || capabilities.contains("calc.units"))
@@ -88,8 +129,16 @@ impl CalcTileHandler {
             && result.is_none()
         {
             result = Calculator::measurement(&keyword, "currencies");
+            if result.is_some() {
+                info = CURRENCIES
+                    .get()
+                    .and_then(Option::as_ref)
+                    .map(Currency::status);
+            }
         }
 
+        *self.info.borrow_mut() = info;
+        *self.actions.borrow_mut() = actions;
         *self.result.borrow_mut() = result;
         self.result.borrow().is_some()
     }
@@ -100,22 +149,46 @@ impl CalcTileHandler {
         if let Some((num, result_text)) = &*self.result.borrow() {
             imp.equation_holder.set_text(&search_query);
             imp.result_holder.set_text(&result_text);
-            self.attrs
-                .borrow_mut()
-                .entry("result".to_string())
-                .or_insert(num.to_string());
+            let info = self.info.borrow();
+            imp.info_holder
+                .set_text(info.as_deref().unwrap_or_default());
+            imp.info_holder.set_visible(info.is_some());
+            let mut attrs = self.attrs.borrow_mut();
+            attrs.insert("result".to_string(), num.to_string());
+            attrs.insert("keyword".to_string(), search_query.to_string());
+            attrs.insert("display".to_string(), result_text.to_string());
         }
 
         Some(())
     }
+    pub fn actions(&self) -> Vec<ApplicationAction> {
+        self.actions.borrow().clone()
+    }
     pub fn change_attrs(&self, key: String, value: String) {
         self.attrs.borrow_mut().insert(key, value);
     }
     pub fn bind_signal(&self, row: &SherlockRow, launcher: Rc<Launcher>) {
         row.add_css_class("calc-tile");
         let attrs = self.attrs.clone();
+        let evaluation = self.evaluation.clone();
         let signal_id = row.connect_local("row-should-activate", false, move |args| {
             let row = args.first().map(|f| f.get::<SherlockRow>().ok())??;
+            if let Some(evaluation) = evaluation.borrow().as_ref() {
+                evaluation.commit();
+            }
+            {
+                let attrs = attrs.borrow();
+                if let (Some(query), Some(result), Some(display)) = (
+                    attrs.get("keyword"),
+                    attrs.get("result"),
+                    attrs.get("display"),
+                ) {
+                    let entry = HistoryEntry::new(query, result, display);
+                    if let Err(e) = history::append(entry) {
+                        let _result = e.insert(false);
+                    }
+                }
+            }
             let param: u8 = args.get(1).and_then(|v| v.get::<u8>().ok())?;
             let param: Option<bool> = match param {
                 1 => Some(false),
@@ -9,94 +9,19 @@ use std::rc::Rc;
 use crate::actions::{execute_from_attrs, get_attrs_map};
 use crate::g_subclasses::sherlock_row::SherlockRow;
 use crate::g_subclasses::tile_item::UpdateHandler;
-use crate::launcher::clipboard_launcher::ClipboardLauncher;
+use crate::launcher::clipboard_launcher::{color::ColorTile, ClipboardLauncher};
 use crate::launcher::Launcher;
-use crate::loader::launcher_loader::COLOR_RE;
 use crate::prelude::{IconComp, TileHandler};
 use crate::ui::g_templates::{AppTile, CalcTile};
 use crate::ui::tiles::calc_tile::CalcTileHandler;
 
 use super::Tile;
 
-struct RGB {
-    r: u8,
-    g: u8,
-    b: u8,
-}
-impl RGB {
-    fn from_hex(hex: &str) -> Self {
-        let default = Self { r: 0, g: 0, b: 0 };
-        if hex.len() >= 6 {
-            let Ok(r) = u8::from_str_radix(&hex[0..2], 16) else {
-                return default;
-            };
-            let Ok(g) = u8::from_str_radix(&hex[2..4], 16) else {
-                return default;
-            };
-            let Ok(b) = u8::from_str_radix(&hex[4..6], 16) else {
-                return default;
-            };
-            return Self { r, g, b };
-        }
-        default
-    }
-    fn from_hsl(hsl: Vec<u32>) -> Self {
-        if hsl.len() != 3 {
-            return Self { r: 0, g: 0, b: 0 };
-        }
-        let (h, s, l) = (hsl[0], hsl[1], hsl[2]);
-
-        let h = (h as f64) / 360.0;
-        let s = (s as f64) / 100.0;
-        let l = (l as f64) / 100.0;
-
-        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
-        let x = c * (1.0 - ((h * 6.0).fract() - 1.0).abs());
-        let m = l - c / 2.0;
-
-        let (r_prime, g_prime, b_prime) = if h >= 0.0 && h < 1.0 / 6.0 {
-            (c, x, 0.0)
-        } else if h >= 1.0 / 6.0 && h < 2.0 / 6.0 {
-            (x, c, 0.0)
-        } else if h >= 2.0 / 6.0 && h < 3.0 / 6.0 {
-            (0.0, c, x)
-        } else if h >= 3.0 / 6.0 && h < 4.0 / 6.0 {
-            (0.0, x, c)
-        } else if h >= 4.0 / 6.0 && h < 5.0 / 6.0 {
-            (x, 0.0, c)
-        } else {
-            (c, 0.0, x)
-        };
-
-        let r = ((r_prime + m) * 255.0).round() as u8;
-        let g = ((g_prime + m) * 255.0).round() as u8;
-        let b = ((b_prime + m) * 255.0).round() as u8;
-        Self { r, g, b }
-    }
-    fn from_str(rgb: &str) -> Self {
-        let rgb: Vec<u8> = rgb
-            .split(",")
-            .map(|s| s.trim())
-            .filter_map(|s| s.parse::<u8>().ok())
-            .collect();
-        if rgb.len() != 3 {
-            return Self { r: 0, g: 0, b: 0 };
-        }
-        Self {
-            r: rgb[0],
-            g: rgb[1],
-            b: rgb[2],
-        }
-    }
-    fn to_vec(&self) -> Vec<u8> {
-        vec![self.r, self.g, self.b]
-    }
-}
-
 impl Tile {
     pub fn clipboard(
         launcher: Rc<Launcher>,
         clp: &ClipboardLauncher,
+        index: Option<u16>,
     ) -> Option<(Widget, UpdateHandler)> {
         let clipboard_content = clp.clipboard_content.clone();
         if clipboard_content.trim().is_empty() {
@@ -141,65 +66,15 @@ impl Tile {
         }
 
         // Color Capabilities
-        if capabilities
-            .iter()
-            .find(|c| c.starts_with("colors."))
-            .is_some()
-            && clipboard_content.len() <= 20
-        {
-            let all = capabilities.contains("colors.all");
+        if let Some(color) = index.and_then(|i| clp.colors.get(i as usize)) {
             let attrs = get_attrs_map(vec![
-                ("method", None),
+                ("method", Some("copy")),
                 ("keyword", Some(&clipboard_content)),
+                ("result", Some(&color.value)),
             ]);
-
-            if let Some(captures) = COLOR_RE.captures(&clipboard_content) {
-                if all || capabilities.contains("colors.rgb") {
-                    if let Some(rgb) = captures.get(2) {
-                        let color = RGB::from_str(rgb.as_str());
-                        let label = format!("rbg({})", rgb.as_str().trim());
-                        let tile = color_tile(color, label);
-                        let handler = ClipboardHandler::new(&tile, attrs);
-                        return Some((tile.upcast::<Widget>(), UpdateHandler::Clipboard(handler)));
-                    }
-                }
-
-                if all || capabilities.contains("colors.hsl") {
-                    if let Some(hsl) = captures.get(3) {
-                        let mut res: Vec<u32> = Vec::with_capacity(3);
-                        let mut tmp = 0;
-                        let mut was_changed: u8 = 0;
-                        hsl.as_str()
-                            .chars()
-                            .filter(|s| !s.is_whitespace())
-                            .for_each(|s| {
-                                if let Some(digit) = s.to_digit(10) {
-                                    tmp = tmp * 10 + digit;
-                                    was_changed = 1;
-                                } else if was_changed > 0 {
-                                    res.push(tmp);
-                                    was_changed = 0;
-                                    tmp = 0;
-                                }
-                            });
-                        let color = RGB::from_hsl(res);
-                        let label = format!("hls({})", hsl.as_str().trim());
-                        let tile = color_tile(color, label);
-                        let handler = ClipboardHandler::new(&tile, attrs);
-                        return Some((tile.upcast::<Widget>(), UpdateHandler::Clipboard(handler)));
-                    }
-                }
-
-                if all || capabilities.contains("colors.hex") {
-                    if let Some(hex) = captures.get(4) {
-                        let color = RGB::from_hex(hex.as_str());
-                        let label = format!("#{}", hex.as_str().trim());
-                        let tile = color_tile(color, label);
-                        let handler = ClipboardHandler::new(&tile, attrs);
-                        return Some((tile.upcast::<Widget>(), UpdateHandler::Clipboard(handler)));
-                    }
-                }
-            }
+            let tile = color_tile(color);
+            let handler = ClipboardHandler::new(&tile, attrs);
+            return Some((tile.upcast::<Widget>(), UpdateHandler::Clipboard(handler)));
         }
 
         // Calculator Capabilities
@@ -220,21 +95,23 @@ impl Tile {
     }
 }
 
-fn color_tile(rgb: RGB, label: String) -> AppTile {
+fn color_tile(color: &ColorTile) -> AppTile {
     let tile = AppTile::new();
     let imp = tile.imp();
 
-    imp.title.set_text(&label);
-    imp.category.set_text("From Clipboard");
-    let pix_buf = rgb.to_vec();
+    imp.title.set_text(&color.title);
+    imp.category.set_text(&color.category);
+    // Multiple colors are drawn as vertical stripes
+    let width = color.swatch.len().max(1) as i32;
+    let pix_buf: Vec<u8> = color.swatch.iter().flatten().copied().collect();
     let image_buf = gdk::gdk_pixbuf::Pixbuf::from_bytes(
         &Bytes::from_owned(pix_buf),
         gdk::gdk_pixbuf::Colorspace::Rgb,
         false,
         8,
+        width,
         1,
-        1,
-        3,
+        3 * width,
     );
     if let Some(image_buf) = image_buf.scale_simple(30, 30, gdk::gdk_pixbuf::InterpType::Nearest) {
         let texture = gtk4::gdk::Texture::for_pixbuf(&image_buf);
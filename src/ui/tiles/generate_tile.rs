@@ -0,0 +1,104 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gio::glib::object::{Cast, ObjectExt};
+use gio::glib::WeakRef;
+use gtk4::subclass::prelude::ObjectSubclassIsExt;
+use gtk4::Box;
+
+use super::Tile;
+use crate::actions::{execute_from_attrs, get_attrs_map};
+use crate::g_subclasses::sherlock_row::SherlockRow;
+use crate::launcher::generate_launcher::Generator;
+use crate::launcher::Launcher;
+use crate::prelude::{IconComp, TileHandler};
+use crate::ui::g_templates::AppTile;
+
+impl Tile {
+    pub fn generate(launcher: Rc<Launcher>) -> AppTile {
+        let tile = AppTile::new();
+        let imp = tile.imp();
+
+        if let Some(name) = &launcher.name {
+            imp.category.set_text(name);
+        } else {
+            imp.category.set_visible(false);
+        }
+
+        tile
+    }
+}
+
+/// Shows what the query generates. The value itself is only created on activation,
+/// so every activation copies a fresh one and secrets never end up in the tile.
+#[derive(Debug)]
+pub struct GenerateTileHandler {
+    tile: WeakRef<AppTile>,
+    attrs: Rc<RefCell<HashMap<String, String>>>,
+    generator: RefCell<Option<Generator>>,
+    password_length: usize,
+}
+impl GenerateTileHandler {
+    pub fn new(launcher: Rc<Launcher>, password_length: usize) -> Self {
+        let attrs = get_attrs_map(vec![
+            ("method", Some(&launcher.method)),
+            ("exit", Some(&launcher.exit.to_string())),
+        ]);
+        Self {
+            tile: WeakRef::new(),
+            attrs: Rc::new(RefCell::new(attrs)),
+            generator: RefCell::new(None),
+            password_length,
+        }
+    }
+    pub fn based_show(&self, keyword: &str) -> bool {
+        let generator = Generator::parse(keyword, self.password_length);
+        let show = generator.is_some();
+        *self.generator.borrow_mut() = generator;
+        show
+    }
+    pub fn update(&self, keyword: &str) -> Option<()> {
+        let generator = (*self.generator.borrow())?;
+        self.attrs
+            .borrow_mut()
+            .insert(String::from("keyword"), keyword.to_string());
+
+        let tile = self.tile.upgrade()?;
+        let imp = tile.imp();
+        imp.title.set_text(&generator.description());
+        imp.icon.set_icon(Some(generator.icon()), None, None);
+
+        Some(())
+    }
+    pub fn change_attrs(&self, key: String, value: String) {
+        self.attrs.borrow_mut().insert(key, value);
+    }
+    pub fn bind_signal(&self, row: &SherlockRow, launcher: Rc<Launcher>) {
+        let signal_id = row.connect_local("row-should-activate", false, {
+            let attrs = self.attrs.clone();
+            move |args| {
+                let row = args.first().map(|f| f.get::<SherlockRow>().ok())??;
+                let param: u8 = args.get(1).and_then(|v| v.get::<u8>().ok())?;
+                let param: Option<bool> = match param {
+                    1 => Some(false),
+                    2 => Some(true),
+                    _ => None,
+                };
+                execute_from_attrs(&row, &attrs.borrow(), param, Some(launcher.clone()));
+                None
+            }
+        });
+        row.set_signal_id(signal_id);
+    }
+    pub fn shortcut(&self) -> Option<Box> {
+        self.tile.upgrade().map(|t| t.imp().shortcut_holder.get())
+    }
+}
+impl TileHandler for GenerateTileHandler {
+    fn replace_tile(&mut self, tile: &gtk4::Widget) {
+        if let Some(tile) = tile.downcast_ref::<AppTile>() {
+            self.tile = tile.downgrade();
+        }
+    }
+}
@@ -4,10 +4,13 @@ pub mod calc_tile;
 pub mod clipboard_tile;
 pub mod error_tile;
 pub mod event_tile;
+pub mod generate_tile;
+pub mod most_used_tile;
 pub mod mpris_tile;
 pub mod pipe_tile;
 pub mod pomodoro_tile;
 pub mod process_tile;
+pub mod transform_tile;
 pub mod util;
 pub mod weather_tile;
 pub mod web_tile;
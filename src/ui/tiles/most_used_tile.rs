@@ -0,0 +1,242 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gio::glib::{BoxedAnyObject, WeakRef};
+use gio::ListStore;
+use gtk4::subclass::prelude::ObjectSubclassIsExt;
+use gtk4::{pango::EllipsizeMode, prelude::*};
+use gtk4::{
+    Align, Box as GtkBox, Image, Label, ListItem, Orientation, PolicyType, SignalListItemFactory,
+    SingleSelection, Widget,
+};
+
+use super::Tile;
+use crate::actions::{execute_from_attrs, get_attrs_map};
+use crate::g_subclasses::sherlock_row::SherlockRow;
+use crate::launcher::most_used_launcher::MostUsedLauncher;
+use crate::launcher::Launcher;
+use crate::loader::util::AppData;
+use crate::prelude::{IconComp, TileHandler};
+use crate::ui::g_templates::GridSearchUi;
+
+/// An app of the grid. The shortcut holder is created up front so shortcuts can be assigned
+/// before the grid has bound its cells.
+struct MostUsedApp {
+    app: AppData,
+    shortcut: GtkBox,
+}
+
+impl Tile {
+    pub fn most_used(launcher: &Launcher, most_used: &MostUsedLauncher) -> Option<GridSearchUi> {
+        let apps = most_used.top_apps();
+        if apps.is_empty() {
+            return None;
+        }
+
+        let tile = GridSearchUi::new();
+        let imp = tile.imp();
+        imp.search_bar_holder.set_visible(false);
+        imp.preview_box.set_visible(false);
+        match &launcher.name {
+            Some(name) => imp.mode_title.set_text(name),
+            None => imp.mode_title_holder.set_visible(false),
+        }
+        // The grid takes the space it needs inside the result list
+        imp.result_viewport
+            .set_policy(PolicyType::Never, PolicyType::Never);
+
+        let model = ListStore::new::<BoxedAnyObject>();
+        for app in apps {
+            model.append(&BoxedAnyObject::new(MostUsedApp {
+                app,
+                shortcut: shortcut_holder(),
+            }));
+        }
+        imp.results
+            .set_model(Some(&SingleSelection::new(Some(model))));
+        imp.results
+            .set_factory(Some(&make_factory(most_used.icon_size)));
+        imp.results.set_max_columns(most_used.limit.max(1) as u32);
+        imp.results.set_single_click_activate(true);
+
+        Some(tile)
+    }
+}
+
+fn shortcut_holder() -> GtkBox {
+    let holder = GtkBox::new(Orientation::Horizontal, 5);
+    holder.set_widget_name("shortcut-holder");
+    holder.set_halign(Align::Center);
+    holder.set_visible(false);
+
+    let modkey = Label::new(None);
+    modkey.set_widget_name("shortcut-modkey");
+    let digit = Label::new(None);
+    digit.set_widget_name("shortcut");
+    holder.append(&modkey);
+    holder.append(&digit);
+    holder
+}
+
+fn make_factory(icon_size: i32) -> SignalListItemFactory {
+    let factory = SignalListItemFactory::new();
+    factory.connect_setup(move |_, item| {
+        let item = item
+            .downcast_ref::<ListItem>()
+            .expect("Item must be a ListItem");
+        let cell = GtkBox::new(Orientation::Vertical, 5);
+        cell.add_css_class("most-used-app");
+
+        let icon = Image::new();
+        icon.set_pixel_size(icon_size);
+        let name = Label::new(None);
+        name.set_ellipsize(EllipsizeMode::End);
+        name.set_max_width_chars(12);
+
+        cell.append(&icon);
+        cell.append(&name);
+        item.set_child(Some(&cell));
+    });
+    factory.connect_bind(|_, item| {
+        let item = item
+            .downcast_ref::<ListItem>()
+            .expect("Item must be a ListItem");
+        let cell = item
+            .child()
+            .and_downcast::<GtkBox>()
+            .expect("Child must be a Box");
+        let data = item
+            .item()
+            .and_downcast::<BoxedAnyObject>()
+            .expect("Item must be a BoxedAnyObject");
+        let data = data.borrow::<MostUsedApp>();
+
+        if let Some(icon) = cell.first_child().and_downcast::<Image>() {
+            icon.set_icon(
+                data.app.icon.as_deref(),
+                data.app.icon_class.as_deref(),
+                Some("application-x-executable"),
+            );
+        }
+        if let Some(name) = cell
+            .first_child()
+            .and_then(|icon| icon.next_sibling())
+            .and_downcast::<Label>()
+        {
+            name.set_text(&data.app.name);
+        }
+        cell.set_tooltip_text(Some(&data.app.name));
+        cell.append(&data.shortcut);
+    });
+    factory.connect_unbind(|_, item| {
+        let item = item
+            .downcast_ref::<ListItem>()
+            .expect("Item must be a ListItem");
+        if let (Some(cell), Some(data)) = (
+            item.child().and_downcast::<GtkBox>(),
+            item.item().and_downcast::<BoxedAnyObject>(),
+        ) {
+            cell.remove(&data.borrow::<MostUsedApp>().shortcut);
+        }
+    });
+    factory
+}
+
+#[derive(Debug, Default)]
+pub struct MostUsedTileHandler {
+    tile: WeakRef<GridSearchUi>,
+    attrs: Rc<RefCell<HashMap<String, String>>>,
+}
+impl MostUsedTileHandler {
+    pub fn new(launcher: Rc<Launcher>) -> Self {
+        // Every cell launches an app
+        let attrs = get_attrs_map(vec![
+            ("method", Some("app_launcher")),
+            ("exit", Some(&launcher.exit.to_string())),
+        ]);
+        Self {
+            tile: WeakRef::new(),
+            attrs: Rc::new(RefCell::new(attrs)),
+        }
+    }
+    pub fn change_attrs(&self, key: String, value: String) {
+        self.attrs.borrow_mut().insert(key, value);
+    }
+    pub fn bind_signal(&self, row: &SherlockRow, launcher: Rc<Launcher>) {
+        row.add_css_class("most-used-tile");
+
+        // Clicked cells are launched through the row as well
+        if let Some(tile) = self.tile.upgrade() {
+            let row = row.downgrade();
+            tile.imp().results.connect_activate(move |_, position| {
+                if let Some(row) = row.upgrade() {
+                    let exit: u8 = 0;
+                    row.emit_by_name::<()>("row-should-activate", &[&exit, &position.to_string()]);
+                }
+            });
+        }
+
+        let signal_id = row.connect_local("row-should-activate", false, {
+            let tile = self.tile.clone();
+            let attrs = self.attrs.clone();
+            move |args| {
+                let row = args.first().map(|f| f.get::<SherlockRow>().ok())??;
+                let param: u8 = args.get(1).and_then(|v| v.get::<u8>().ok())?;
+                let callback: String = args.get(2).and_then(|v| v.get::<String>().ok())?;
+                let exit: Option<bool> = match param {
+                    1 => Some(false),
+                    2 => Some(true),
+                    _ => None,
+                };
+
+                // Shortcuts and clicks pass the position of the cell, <return> launches the
+                // selected one
+                let tile = tile.upgrade()?;
+                let selection = tile
+                    .imp()
+                    .results
+                    .model()
+                    .and_downcast::<SingleSelection>()?;
+                let position = callback.parse::<u32>().unwrap_or(selection.selected());
+                let data = selection.item(position).and_downcast::<BoxedAnyObject>()?;
+
+                let mut attrs = attrs.borrow().clone();
+                {
+                    let app = &data.borrow::<MostUsedApp>().app;
+                    attrs.insert(String::from("exec"), app.exec.clone()?);
+                    attrs.insert(String::from("term"), app.terminal.to_string());
+                    attrs.insert(String::from("name"), app.name.clone());
+                    if let Some(icon) = &app.icon {
+                        attrs.insert(String::from("icon"), icon.clone());
+                    }
+                    if let Some(path) = app.desktop_file.as_ref().and_then(|p| p.to_str()) {
+                        attrs.insert(String::from("desktop_file"), path.to_string());
+                    }
+                }
+                execute_from_attrs(&row, &attrs, exit, Some(launcher.clone()));
+                // To reload ui according to mode
+                let _ = row.activate_action("win.update-items", Some(&false.to_variant()));
+                None
+            }
+        });
+        row.set_signal_id(signal_id);
+    }
+    /// Shortcut holders of the cells, in order
+    pub fn shortcuts(&self) -> Vec<GtkBox> {
+        let Some(model) = self.tile.upgrade().and_then(|t| t.imp().results.model()) else {
+            return vec![];
+        };
+        (0..model.n_items())
+            .filter_map(|i| model.item(i).and_downcast::<BoxedAnyObject>())
+            .map(|data| data.borrow::<MostUsedApp>().shortcut.clone())
+            .collect()
+    }
+}
+impl TileHandler for MostUsedTileHandler {
+    fn replace_tile(&mut self, tile: &Widget) {
+        if let Some(tile) = tile.downcast_ref::<GridSearchUi>() {
+            self.tile = tile.downgrade()
+        }
+    }
+}
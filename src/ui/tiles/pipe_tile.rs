@@ -105,11 +105,18 @@ impl PipeTileHandler {
                     .map(|(k, v)| (k.as_str(), Some(v.as_str())))
                     .collect()
             });
+        // Keep the raw hidden map around for json output
+        let hidden = pipe
+            .hidden
+            .as_ref()
+            .and_then(|h| serde_json::to_string(h).ok());
         constructor.extend(vec![
             ("method", Some(method)),
             ("result", result),
             ("field", pipe.field.as_deref()),
             ("exit", Some(&exit)),
+            ("hidden", hidden.as_deref()),
+            ("next_content", launcher.next_content.as_deref()),
         ]);
         let attrs = get_attrs_map(constructor);
         Self {
@@ -0,0 +1,107 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gio::glib::object::{Cast, ObjectExt};
+use gio::glib::WeakRef;
+use gtk4::subclass::prelude::ObjectSubclassIsExt;
+use gtk4::Box;
+
+use super::Tile;
+use crate::actions::{execute_from_attrs, get_attrs_map};
+use crate::g_subclasses::sherlock_row::SherlockRow;
+use crate::launcher::transform_launcher::Transform;
+use crate::launcher::Launcher;
+use crate::prelude::{IconComp, TileHandler};
+use crate::ui::g_templates::AppTile;
+
+/// Longer results are cut off in the title, but copied in full
+const PREVIEW_LENGTH: usize = 80;
+
+impl Tile {
+    pub fn transform(operation: Transform) -> AppTile {
+        let tile = AppTile::new();
+        let imp = tile.imp();
+
+        imp.category.set_text(operation.name());
+        imp.icon.set_icon(Some(operation.icon()), None, None);
+
+        tile
+    }
+}
+
+#[derive(Debug)]
+pub struct TransformTileHandler {
+    tile: WeakRef<AppTile>,
+    attrs: Rc<RefCell<HashMap<String, String>>>,
+    result: RefCell<Option<String>>,
+}
+impl TransformTileHandler {
+    pub fn new(launcher: Rc<Launcher>) -> Self {
+        let attrs = get_attrs_map(vec![
+            ("method", Some(&launcher.method)),
+            ("exit", Some(&launcher.exit.to_string())),
+        ]);
+        Self {
+            tile: WeakRef::new(),
+            attrs: Rc::new(RefCell::new(attrs)),
+            result: RefCell::new(None),
+        }
+    }
+    /// Applies the operation to the text. Tiles are hidden if the operation does not apply.
+    pub fn based_show(&self, text: &str, operation: Transform) -> bool {
+        let result = operation.apply(text);
+        let show = result.is_some();
+        *self.result.borrow_mut() = result;
+        show
+    }
+    pub fn update(&self, keyword: &str) -> Option<()> {
+        let result = self.result.borrow();
+        let result = result.as_ref()?;
+        {
+            let mut attrs = self.attrs.borrow_mut();
+            attrs.insert(String::from("keyword"), keyword.to_string());
+            attrs.insert(String::from("result"), result.clone());
+        }
+
+        let tile = self.tile.upgrade()?;
+        let first_line = result.lines().next().unwrap_or_default();
+        let mut preview: String = first_line.chars().take(PREVIEW_LENGTH).collect();
+        if preview.len() < result.len() {
+            preview.push('…');
+        }
+        tile.imp().title.set_text(&preview);
+
+        Some(())
+    }
+    pub fn change_attrs(&self, key: String, value: String) {
+        self.attrs.borrow_mut().insert(key, value);
+    }
+    pub fn bind_signal(&self, row: &SherlockRow, launcher: Rc<Launcher>) {
+        let signal_id = row.connect_local("row-should-activate", false, {
+            let attrs = self.attrs.clone();
+            move |args| {
+                let row = args.first().map(|f| f.get::<SherlockRow>().ok())??;
+                let param: u8 = args.get(1).and_then(|v| v.get::<u8>().ok())?;
+                let param: Option<bool> = match param {
+                    1 => Some(false),
+                    2 => Some(true),
+                    _ => None,
+                };
+                execute_from_attrs(&row, &attrs.borrow(), param, Some(launcher.clone()));
+                None
+            }
+        });
+        row.set_signal_id(signal_id);
+    }
+    pub fn shortcut(&self) -> Option<Box> {
+        self.tile.upgrade().map(|t| t.imp().shortcut_holder.get())
+    }
+}
+impl TileHandler for TransformTileHandler {
+    fn replace_tile(&mut self, tile: &gtk4::Widget) {
+        if let Some(tile) = tile.downcast_ref::<AppTile>() {
+            self.tile = tile.downgrade();
+        }
+    }
+}
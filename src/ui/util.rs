@@ -10,11 +10,13 @@ use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 use std::u32;
 
-use gio::glib::{self, Object, WeakRef};
+use gio::glib::{self, MainContext, Object, WeakRef};
 use gio::ListStore;
 use gtk4::gdk::{Key, ModifierType};
+use gtk4::subclass::prelude::ObjectSubclassIsExt;
 use gtk4::{
     prelude::*, Box as GtkBox, CustomFilter, CustomSorter, Entry, Justification, Label, ListView,
     ScrolledWindow, SignalListItemFactory, Spinner, Widget,
@@ -22,12 +24,12 @@ use gtk4::{
 use serde::Deserialize;
 
 use crate::g_subclasses::tile_item::TileItem;
-use crate::launcher::{Launcher, LauncherType};
-use crate::loader::Loader;
-use crate::sherlock_error;
+use crate::launcher::{app_launcher::AppLauncher, Launcher, LauncherType};
+use crate::loader::{application_watcher::ApplicationWatcher, Loader};
 use crate::utils::config::{BindDefaults, ConfigGuard};
 use crate::utils::errors::{SherlockError, SherlockErrorType};
 use crate::utils::paths;
+use crate::{sher_log, sherlock_error};
 
 use super::tiles::util::TextViewTileBuilder;
 
@@ -392,12 +394,113 @@ impl SearchHandler {
             let weaks: Vec<WeakRef<TileItem>> = rows
                 .into_iter()
                 .filter(|t| t.is_async())
-                .map(|row| row.downgrade())
+                .map(|row| {
+                    row.set_model(&model);
+                    row.downgrade()
+                })
                 .collect();
             update_async(weaks, &self.task, String::new());
             *self.modes.borrow_mut() = holder;
         }
     }
+    /// Keeps the app tiles and the app cache in sync with the application directories
+    pub fn watch_applications(&self) {
+        let (sender, receiver) = async_channel::unbounded();
+        if let Err(e) = ApplicationWatcher::spawn(sender) {
+            let _ = e.insert(false);
+            return;
+        }
+        let model = self.model.clone();
+        MainContext::default().spawn_local(async move {
+            while let Ok(mut changed) = receiver.recv().await {
+                // Package managers usually touch several files at once
+                glib::timeout_future(Duration::from_millis(500)).await;
+                while let Ok(more) = receiver.try_recv() {
+                    changed.extend(more);
+                }
+                if let Some(model) = model.as_ref().and_then(|m| m.upgrade()) {
+                    update_applications(&model, changed).await;
+                }
+            }
+        });
+    }
+}
+
+/// Replaces the tiles of changed desktop files. `changed` may contain files and directories.
+/// The app cache is updated even if no app tiles are shown. Desktop files are read off the
+/// main thread, only the model is updated on it.
+pub async fn update_applications(model: &ListStore, changed: Vec<PathBuf>) {
+    let (template, tiles) = app_tiles(model);
+    let app_launcher = template
+        .as_ref()
+        .and_then(|launcher| match &launcher.launcher_type {
+            LauncherType::App(app_launcher) => Some(app_launcher.clone()),
+            _ => None,
+        });
+    // Cached priorities are recomputed on load, keywords are used by default
+    let (priority, use_keywords) = app_launcher
+        .as_ref()
+        .map_or((0.0, true), |app| (app.priority, app.use_keywords));
+
+    let known: Vec<PathBuf> = tiles.into_iter().map(|(_, file)| file).collect();
+    let reload = gio::spawn_blocking(move || {
+        Loader::reload_applications(&changed, &known, priority, use_keywords)
+    });
+    let (outdated, apps) = match reload.await {
+        Ok(Ok(r)) => r,
+        Ok(Err(e)) => {
+            let _ = e.insert(false);
+            return;
+        }
+        Err(_) => return,
+    };
+    let _ = sher_log!(format!(
+        "Applications changed: {} removed, {} added",
+        outdated.len(),
+        apps.len()
+    ));
+
+    // Tiles are only updated while an app launcher is active
+    let (Some(template), Some(app_launcher)) = (template, app_launcher) else {
+        return;
+    };
+    // The model might have changed during the reload
+    let (_, tiles) = app_tiles(model);
+    let _freeze_guard = model.freeze_notify();
+    tiles
+        .iter()
+        .rev()
+        .filter(|(_, file)| outdated.contains(file))
+        .for_each(|(i, _)| model.remove(*i));
+    if !apps.is_empty() {
+        let launcher = Rc::new(template.with_type(LauncherType::App(AppLauncher {
+            apps,
+            priority: app_launcher.priority,
+            use_keywords: app_launcher.use_keywords,
+            customize: app_launcher.customize,
+        })));
+        let rows = launcher.bind_obj(launcher.clone());
+        model.extend_from_slice(&rows);
+    }
+}
+/// The first app launcher of the model and the positions of its tiles' desktop files
+fn app_tiles(model: &ListStore) -> (Option<Rc<Launcher>>, Vec<(u32, PathBuf)>) {
+    let mut template: Option<Rc<Launcher>> = None;
+    let mut tiles: Vec<(u32, PathBuf)> = Vec::new();
+    for i in 0..model.n_items() {
+        let Some(item) = model.item(i).and_downcast::<TileItem>() else {
+            continue;
+        };
+        let launcher = item.imp().launcher.borrow().clone();
+        if !matches!(launcher.launcher_type, LauncherType::App(_)) {
+            continue;
+        }
+        if let Some(file) = item.get_by_key(|app| app.desktop_file.clone()).flatten() {
+            tiles.push((i, file));
+        }
+        template.get_or_insert(launcher);
+    }
+    (template, tiles)
 }
 
 #[derive(Clone)]
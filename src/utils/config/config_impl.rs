@@ -197,6 +197,7 @@ impl SherlockConfig {
         config.runtime.display_raw = sherlock_flags.display_raw;
         config.runtime.photo_mode = sherlock_flags.photo_mode;
         config.runtime.field = sherlock_flags.field.take();
+        config.runtime.output_format = sherlock_flags.output_format.take().unwrap_or_default();
         config.runtime.daemonize = sherlock_flags.daemonize;
 
         if let Some(placeholder) = sherlock_flags.placeholder.take() {
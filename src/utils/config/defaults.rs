@@ -1,11 +1,10 @@
 use std::{path::PathBuf, process::Command};
 
 use crate::{
-    loader::application_loader::{get_applications_dir, get_desktop_files},
+    loader::mime_apps::MIME_APPS,
     sherlock_error,
     utils::{
         errors::{SherlockError, SherlockErrorType},
-        files::read_lines,
         paths,
     },
 };
@@ -67,53 +66,29 @@ impl ConstantDefaults {
     fn is_terminal_installed(terminal: &str) -> bool {
         Command::new(terminal).arg("--version").output().is_ok()
     }
+    /// The default handler for web links according to `mimeapps.list`. Only resolved where
+    /// the browser is needed, the associations depend on the loaded config.
     pub fn browser() -> Result<String, SherlockError> {
-        // Find default browser desktop file
-        let output = Command::new("xdg-settings")
-            .arg("get")
-            .arg("default-web-browser")
-            .output()
-            .map_err(|e| {
-                sherlock_error!(
-                    SherlockErrorType::EnvVarNotFoundError(String::from("default browser")),
-                    e.to_string()
-                )
-            })?;
-
-        let desktop_file: String = if output.status.success() {
-            String::from_utf8_lossy(&output.stdout).trim().to_string()
-        } else {
-            return Err(sherlock_error!(
-                SherlockErrorType::EnvVarNotFoundError("default browser".to_string()),
+        let mime_apps = MIME_APPS.read().map_err(|e| {
+            sherlock_error!(
+                SherlockErrorType::EnvVarNotFoundError(String::from("default browser")),
+                e.to_string()
+            )
+        })?;
+        [
+            "x-scheme-handler/https",
+            "x-scheme-handler/http",
+            "text/html",
+        ]
+        .iter()
+        .find_map(|mime| mime_apps.default_app(mime))
+        .map(|app| app.exec)
+        .ok_or_else(|| {
+            sherlock_error!(
+                SherlockErrorType::EnvVarNotFoundError(String::from("default browser")),
                 ""
-            ));
-        };
-        let desktop_dirs = get_applications_dir();
-        let desktop_files = get_desktop_files(desktop_dirs);
-        let browser_file = desktop_files
-            .iter()
-            .find(|f| f.ends_with(&desktop_file))
-            .ok_or_else(|| {
-                sherlock_error!(
-                    SherlockErrorType::EnvVarNotFoundError("default browser".to_string()),
-                    ""
-                )
-            })?;
-        // read default browser desktop file
-        let browser = read_lines(browser_file)
-            .map_err(|e| {
-                sherlock_error!(
-                    SherlockErrorType::FileReadError(browser_file.clone()),
-                    e.to_string()
-                )
-            })?
-            .filter_map(Result::ok)
-            .find(|line| line.starts_with("Exec="))
-            .and_then(|line| line.strip_prefix("Exec=").map(|l| l.to_string()))
-            .ok_or_else(|| {
-                sherlock_error!(SherlockErrorType::FileParseError(browser_file.clone()), "")
-            })?;
-        Ok(browser)
+            )
+        })
     }
     pub fn teams() -> String {
         String::from("teams-for-linux --enable-features=UseOzonePlatform --ozone-platform=wayland --url {meeting_url}")
@@ -136,6 +111,30 @@ impl ConstantDefaults {
     pub fn currency() -> String {
         String::from("eur")
     }
+    pub fn areas() -> String {
+        String::from("m2")
+    }
+    pub fn speeds() -> String {
+        String::from("km/h")
+    }
+    pub fn data() -> String {
+        String::from("MB")
+    }
+    pub fn durations() -> String {
+        String::from("min")
+    }
+    pub fn pressures() -> String {
+        String::from("bar")
+    }
+    pub fn energies() -> String {
+        String::from("kJ")
+    }
+    pub fn powers() -> String {
+        String::from("kW")
+    }
+    pub fn angles() -> String {
+        String::from("deg")
+    }
 }
 
 pub struct BindDefaults {}
@@ -237,4 +236,7 @@ impl OtherDefaults {
     pub fn placeholder() -> String {
         String::from("Search:")
     }
+    pub fn significant_figures() -> usize {
+        6
+    }
 }
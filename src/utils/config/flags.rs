@@ -1,7 +1,7 @@
 use crate::{
     sherlock_error,
     utils::{
-        config::{ConfigSourceFiles, SherlockConfig},
+        config::{ConfigSourceFiles, OutputFormat, SherlockConfig},
         errors::{SherlockError, SherlockErrorType},
         files::{expand_path, home_dir},
         paths,
@@ -23,6 +23,7 @@ pub struct SherlockFlags {
     pub daemonize: bool,
     pub method: Option<String>,
     pub field: Option<String>,
+    pub output_format: Option<OutputFormat>,
     pub sub_menu: Option<String>,
     pub multi: bool,
     pub photo_mode: bool,
@@ -1,13 +1,14 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
 };
 
 use crate::utils::{
     config::{
         defaults::{BindDefaults, ConstantDefaults, FileDefaults, OtherDefaults},
-        ConfigAppearance, ConfigBackdrop, ConfigBehavior, ConfigBinds, ConfigCaching, ConfigDebug,
-        ConfigDefaultApps, ConfigExpand, ConfigFiles, ConfigUnits, SearchBarIcon, StatusBar,
+        ConfigAppearance, ConfigBackdrop, ConfigBehavior, ConfigBinds, ConfigCaching, ConfigCalc,
+        ConfigDebug, ConfigDefaultApps, ConfigExpand, ConfigFiles, ConfigUnits, SearchBarIcon,
+        StatusBar,
     },
     files::home_dir,
 };
@@ -18,7 +19,8 @@ impl Default for ConfigDefaultApps {
             teams: ConstantDefaults::teams(),
             calendar_client: ConstantDefaults::calendar_client(),
             terminal: ConstantDefaults::get_terminal().unwrap_or_default(), // Should never get to this...
-            browser: ConstantDefaults::browser().ok(),
+            // Resolved from `mimeapps.list` where it is used
+            browser: None,
             mpris: None,
         }
     }
@@ -32,6 +34,25 @@ impl Default for ConfigUnits {
             volumes: ConstantDefaults::volumes(),
             temperatures: ConstantDefaults::temperatures(),
             currency: ConstantDefaults::currency(),
+            areas: ConstantDefaults::areas(),
+            speeds: ConstantDefaults::speeds(),
+            data: ConstantDefaults::data(),
+            durations: ConstantDefaults::durations(),
+            pressures: ConstantDefaults::pressures(),
+            energies: ConstantDefaults::energies(),
+            powers: ConstantDefaults::powers(),
+            angles: ConstantDefaults::angles(),
+        }
+    }
+}
+
+impl Default for ConfigCalc {
+    fn default() -> Self {
+        Self {
+            locale: None,
+            significant_figures: OtherDefaults::significant_figures(),
+            constants: HashMap::new(),
+            functions: HashMap::new(),
         }
     }
 }
@@ -76,6 +97,8 @@ impl Default for ConfigBehavior {
             use_lr_nav: false,
             remember_query: false,
             n_clicks: Some(2),
+            launch_strategy: Default::default(),
+            launch_template: None,
         }
     }
 }
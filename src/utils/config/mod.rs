@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     path::PathBuf,
+    str::FromStr,
 };
 
 use crate::ui::event_port::UIFunction;
@@ -27,6 +28,10 @@ pub struct SherlockConfig {
     #[serde(default)]
     pub units: ConfigUnits,
 
+    /// Calculator extensions (e.g., constants, functions)
+    #[serde(default)]
+    pub calc: ConfigCalc,
+
     /// Debugging preferences (e.g., whether to display errors)
     #[serde(default)]
     pub debug: ConfigDebug,
@@ -101,6 +106,59 @@ pub struct ConfigUnits {
     pub temperatures: String,
     #[serde(default = "ConstantDefaults::currency")]
     pub currency: String,
+    #[serde(default = "ConstantDefaults::areas")]
+    pub areas: String,
+    #[serde(default = "ConstantDefaults::speeds")]
+    pub speeds: String,
+    #[serde(default = "ConstantDefaults::data")]
+    pub data: String,
+    #[serde(default = "ConstantDefaults::durations")]
+    pub durations: String,
+    #[serde(default = "ConstantDefaults::pressures")]
+    pub pressures: String,
+    #[serde(default = "ConstantDefaults::energies")]
+    pub energies: String,
+    #[serde(default = "ConstantDefaults::powers")]
+    pub powers: String,
+    #[serde(default = "ConstantDefaults::angles")]
+    pub angles: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ConfigCalc {
+    /// Locale used to parse and format numbers in conversions, e.g. `de_DE`
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Significant figures shown in conversion results
+    #[serde(default = "OtherDefaults::significant_figures")]
+    pub significant_figures: usize,
+    /// Named constants, e.g. `g = 9.81`
+    #[serde(default)]
+    pub constants: HashMap<String, f64>,
+    /// Functions keyed by their signature, e.g. `"hyp(a, b)" = "sqrt(a^2 + b^2)"`
+    #[serde(default)]
+    pub functions: HashMap<String, String>,
+}
+impl ConfigCalc {
+    /// Returns the parameter names and body of a user-defined function
+    pub fn function(&self, name: &str) -> Option<(Vec<String>, &str)> {
+        self.functions.iter().find_map(|(signature, body)| {
+            let (fn_name, params) = match signature.split_once('(') {
+                Some((fn_name, params)) => (fn_name, params.trim_end().strip_suffix(')')?),
+                None => (signature.as_str(), ""),
+            };
+            if fn_name.trim() != name {
+                return None;
+            }
+            let params = params
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect();
+            Some((params, body.as_str()))
+        })
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -158,6 +216,25 @@ pub struct ConfigBehavior {
     pub n_clicks: Option<u8>,
     #[serde(default)]
     pub remember_query: bool,
+    #[serde(default)]
+    pub launch_strategy: LaunchStrategy,
+    #[serde(default)]
+    pub launch_template: Option<String>,
+}
+
+/// Controls how apps and commands are started
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchStrategy {
+    /// Spawns the process as a detached child of Sherlock
+    #[default]
+    Direct,
+    /// Places the process in its own `app-*.scope` through `systemd-run --user --scope`
+    Systemd,
+    /// Starts the process through `uwsm app --`
+    Uwsm,
+    /// Uses `launch_template`, where `{cmd}` is replaced by the command
+    Custom,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -214,6 +291,32 @@ pub struct Runtime {
     pub daemonize: bool,
     #[serde(default)]
     pub field: Option<String>,
+    #[serde(default)]
+    pub output_format: OutputFormat,
+}
+
+/// Controls how selected rows are written to stdout (or the response socket) in pipe mode.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Every selected result is terminated by a newline
+    #[default]
+    Newline,
+    /// Every selected result is terminated by a null byte, similar to `find -print0`
+    Null,
+    /// Every selected element is printed as a json object on its own line
+    Json,
+}
+impl FromStr for OutputFormat {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "newline" => Ok(Self::Newline),
+            "null" => Ok(Self::Null),
+            "json" => Ok(Self::Json),
+            _ => Err(()),
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -104,6 +104,7 @@ pub enum SherlockErrorType {
     DirReadError(String),
     DirCreateError(String),
     DirRemoveError(String),
+    DirWatchError(String),
 
     // Config & Flags
     ConfigError(Option<String>),
@@ -123,6 +124,10 @@ pub enum SherlockErrorType {
     // Commands
     CommandExecutionError(String),
 
+    // Plugins
+    PluginError(String),
+    ScriptError(String),
+
     // DBus
     DBusConnectionError,
     DBusMessageConstructError(String),
@@ -131,6 +136,9 @@ pub enum SherlockErrorType {
     // Lockfile
     LockfileExistsError,
 
+    // Randomness
+    RandomSourceError,
+
     // Networking
     HttpRequestError(String),
 
@@ -205,6 +213,7 @@ impl SherlockErrorType {
             SherlockErrorType::DirReadError(dir) => dir_msg("read/access", dir),
             SherlockErrorType::DirCreateError(dir) => dir_msg("create", dir),
             SherlockErrorType::DirRemoveError(dir) => dir_msg("remove", dir),
+            SherlockErrorType::DirWatchError(dir) => dir_msg("watch", dir),
 
             // Config & Flags
             SherlockErrorType::ConfigError(val) => {
@@ -234,6 +243,14 @@ impl SherlockErrorType {
                 format!("Failed to execute command \"{}\"", cmd)
             }
 
+            // Plugins
+            SherlockErrorType::PluginError(plugin) => {
+                format!("Plugin \"{}\" failed to handle a request", plugin)
+            }
+            SherlockErrorType::ScriptError(script) => {
+                format!("Failed to compile script \"{}\"", script)
+            }
+
             // DBus
             SherlockErrorType::DBusConnectionError => "Failed to connect to system DBus".into(),
             SherlockErrorType::DBusMessageConstructError(message) => {
@@ -248,6 +265,11 @@ impl SherlockErrorType {
                 format!("Lockfile already exists in the specified location")
             }
 
+            // Randomness
+            SherlockErrorType::RandomSourceError => {
+                "Failed to read from the system's random number generator".into()
+            }
+
             // Networking
             SherlockErrorType::HttpRequestError(resource) => {
                 format!("Failed to get requested source \"{}\"", resource)